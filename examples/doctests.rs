@@ -0,0 +1,14 @@
+use paradocs::{parse_document, write_doctests};
+use scraper::Html;
+
+fn main() {
+    let std_option_option = include_str!("std_option_option.html");
+    let std_option_option_html = Html::parse_document(std_option_option);
+    let document = parse_document(&std_option_option_html).unwrap();
+
+    let dir = std::env::temp_dir().join("paradocs-doctests");
+    for file in write_doctests(&document, &dir).unwrap() {
+        println!("{}", file.display());
+        println!("{}", std::fs::read_to_string(&file).unwrap());
+    }
+}
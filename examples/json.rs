@@ -0,0 +1,14 @@
+use paradocs::{parse_document, Document};
+use scraper::Html;
+
+fn main() {
+    let std_option_option = include_str!("std_option_option.html");
+    let std_option_option_html = Html::parse_document(std_option_option);
+    let document = parse_document(&std_option_option_html).unwrap();
+
+    let json = serde_json::to_string_pretty(&document).unwrap();
+    println!("{}", json);
+
+    let owned: Document<'static> = serde_json::from_str(&json).unwrap();
+    assert_eq!(json, serde_json::to_string_pretty(&owned).unwrap());
+}
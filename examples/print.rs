@@ -1,4 +1,4 @@
-use paradocs::parse_document;
+use paradocs::{parse_document, parse_sidebar_items};
 use scraper::Html;
 
 fn main() {
@@ -21,4 +21,51 @@ fn main() {
     let scraper_html_html = include_str!("scraper_html_html.html");
     let scraper_html_html_html = Html::parse_document(scraper_html_html);
     println!("{:#?}", parse_document(&scraper_html_html_html));
+
+    let std_collections = include_str!("std_collections.html");
+    let std_collections_html = Html::parse_document(std_collections);
+    println!("{:#?}", parse_document(&std_collections_html));
+
+    let std_option_option = include_str!("std_option_option.html");
+    let std_option_option_html = Html::parse_document(std_option_option);
+    println!("{:#?}", parse_document(&std_option_option_html));
+
+    let std_time = include_str!("std_time.html");
+    let std_time_html = Html::parse_document(std_time);
+    println!("{:#?}", parse_document(&std_time_html));
+
+    let serde_core_de_seq_access = include_str!("serde_core_de_seq_access.html");
+    let serde_core_de_seq_access_html = Html::parse_document(serde_core_de_seq_access);
+    println!("{:#?}", parse_document(&serde_core_de_seq_access_html));
+
+    let lock_api_raw_mutex = include_str!("lock_api_raw_mutex.html");
+    let lock_api_raw_mutex_html = Html::parse_document(lock_api_raw_mutex);
+    println!("{:#?}", parse_document(&lock_api_raw_mutex_html));
+
+    let std_io_pipe = include_str!("std_io_pipe.html");
+    let std_io_pipe_html = Html::parse_document(std_io_pipe);
+    println!("{:#?}", parse_document(&std_io_pipe_html));
+
+    let std_arch_is_riscv_feature_detected =
+        include_str!("std_arch_is_riscv_feature_detected.html");
+    let std_arch_is_riscv_feature_detected_html =
+        Html::parse_document(std_arch_is_riscv_feature_detected);
+    println!(
+        "{:#?}",
+        parse_document(&std_arch_is_riscv_feature_detected_html)
+    );
+
+    let std_hint_black_box = include_str!("std_hint_black_box.html");
+    let std_hint_black_box_html = Html::parse_document(std_hint_black_box);
+    println!("{:#?}", parse_document(&std_hint_black_box_html));
+
+    let std_io_repeat = include_str!("std_io_repeat.html");
+    let std_io_repeat_html = Html::parse_document(std_io_repeat);
+    println!("{:#?}", parse_document(&std_io_repeat_html));
+
+    let std_option_sidebar_items = include_str!("std_option_sidebar_items.js");
+    println!("{:#?}", parse_sidebar_items(std_option_sidebar_items));
+
+    let scraper_html_sidebar_items = include_str!("scraper_html_sidebar_items.js");
+    println!("{:#?}", parse_sidebar_items(scraper_html_sidebar_items));
 }
@@ -0,0 +1 @@
+initSidebarItems({"mod":[["tree_sink","Implementation of the \"html5ever\" TreeSink trait."]],"struct":[["Html","An HTML tree."]]});
@@ -0,0 +1 @@
+window.SIDEBAR_ITEMS = {"enum":["Option"],"struct":["IntoIter","Iter","IterMut"]};
@@ -0,0 +1,89 @@
+use std::borrow::Cow;
+
+use ego_tree::NodeRef;
+use scraper::Node;
+
+use crate::{
+    header::{Footnote, Section},
+    item::{Impl, Item, ItemRow},
+    ItemListing,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub enum AnchorTarget<'d, 'a> {
+    Section(&'d Section<'a>),
+    Row(&'d ItemRow<'a>),
+    Item(&'d Item<'a>),
+    Impl(&'d Impl<'a>),
+    Footnote(&'d Footnote<'a>),
+    Listing(&'d ItemListing<'a>),
+}
+
+pub fn parse_anchor(node: NodeRef<Node>) -> Option<Cow<str>> {
+    node.value()
+        .as_element()
+        .and_then(|element| element.id())
+        .map(Cow::Borrowed)
+}
+
+// Rustdoc gives item rows no `id`, so a row is named after the page its link points to:
+// `struct.Sleep.html` becomes `struct.Sleep` and `error/index.html` becomes `mod.error`.
+pub fn parse_row_anchor(maybe_link: NodeRef<Node>) -> Option<Cow<str>> {
+    let href = maybe_link.value().as_element()?.attr("href")?;
+    let path = href.split(['#', '?']).next()?;
+    let mut segments = path.rsplit('/');
+    match segments.next()? {
+        "index.html" => {
+            let module = segments
+                .next()
+                .filter(|module| !module.is_empty() && *module != "." && *module != "..")?;
+            Some(Cow::Owned(format!("mod.{}", module)))
+        }
+        file => file.strip_suffix(".html").map(Cow::Borrowed),
+    }
+}
+
+pub fn find_section<'d, 'a>(
+    sections: &'d [Section<'a>],
+    anchor: &str,
+) -> Option<AnchorTarget<'d, 'a>> {
+    sections
+        .iter()
+        .find(|section| section.anchor.as_deref() == Some(anchor))
+        .map(AnchorTarget::Section)
+        .or_else(|| {
+            sections
+                .iter()
+                .flat_map(|section| &section.footnotes)
+                .find(|footnote| footnote.id == anchor)
+                .map(AnchorTarget::Footnote)
+        })
+}
+
+pub fn find_item<'d, 'a>(items: &'d [Item<'a>], anchor: &str) -> Option<AnchorTarget<'d, 'a>> {
+    items.iter().find_map(|item| {
+        if item.anchor.as_deref() == Some(anchor) {
+            Some(AnchorTarget::Item(item))
+        } else {
+            item.description
+                .as_deref()
+                .and_then(|description| find_section(description, anchor))
+        }
+    })
+}
+
+pub fn find_impl<'d, 'a>(impls: &'d [Impl<'a>], anchor: &str) -> Option<AnchorTarget<'d, 'a>> {
+    impls.iter().find_map(|implementation| {
+        if implementation.anchor.as_deref() == Some(anchor) {
+            Some(AnchorTarget::Impl(implementation))
+        } else {
+            find_item(&implementation.items, anchor)
+        }
+    })
+}
+
+pub fn find_row<'d, 'a>(rows: &'d [ItemRow<'a>], anchor: &str) -> Option<AnchorTarget<'d, 'a>> {
+    rows.iter()
+        .find(|row| row.anchor.as_deref() == Some(anchor))
+        .map(AnchorTarget::Row)
+}
@@ -1,86 +1,499 @@
+use std::borrow::Cow;
+
 use ego_tree::NodeRef;
-use scraper::Node;
+use scraper::{node::Element, ElementRef, Node};
 use selectors::attr::CaseSensitivity;
 
-#[derive(Debug)]
+use crate::{
+    error::{class_list, expect_element, Expected, ParseError, Stage},
+    url::resolve_url,
+};
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum Paragraph<'a> {
     Text(Vec<TextPart<'a>>),
-    List(Vec<Vec<TextPart<'a>>>),
-    Code(Vec<TextPart<'a>>),
+    List(List<'a>),
+    Code(CodeBlock<'a>),
+    BlockQuote(Vec<Paragraph<'a>>),
+    Table(Table<'a>),
+    DefinitionList(Vec<Definition<'a>>),
+    Rule,
+    /// A `<div>` with an admonition class such as `warning` or `note`; `kind` is that class.
+    Admonition {
+        kind: Cow<'a, str>,
+        contents: Vec<Paragraph<'a>>,
+    },
+    /// The outer HTML of a block the parser does not know.
+    RawHtml(Cow<'a, str>),
 }
 
-pub fn parse_p(maybe_p: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let p = maybe_p.value().as_element()?;
+impl Paragraph<'_> {
+    pub fn into_owned(self) -> Paragraph<'static> {
+        match self {
+            Paragraph::Text(text) => Paragraph::Text(into_owned_parts(text)),
+            Paragraph::List(list) => Paragraph::List(list.into_owned()),
+            Paragraph::Code(code) => Paragraph::Code(code.into_owned()),
+            Paragraph::BlockQuote(contents) => {
+                Paragraph::BlockQuote(contents.into_iter().map(Paragraph::into_owned).collect())
+            }
+            Paragraph::Table(table) => Paragraph::Table(table.into_owned()),
+            Paragraph::DefinitionList(definitions) => Paragraph::DefinitionList(
+                definitions
+                    .into_iter()
+                    .map(Definition::into_owned)
+                    .collect(),
+            ),
+            Paragraph::Rule => Paragraph::Rule,
+            Paragraph::Admonition { kind, contents } => Paragraph::Admonition {
+                kind: into_owned(kind),
+                contents: contents.into_iter().map(Paragraph::into_owned).collect(),
+            },
+            Paragraph::RawHtml(html) => Paragraph::RawHtml(into_owned(html)),
+        }
+    }
 
-    if p.name() != "p" {
-        return None;
+    pub(crate) fn refers_to_footnote(&self, id: &str) -> bool {
+        let in_blocks =
+            |blocks: &[Paragraph]| blocks.iter().any(|block| block.refers_to_footnote(id));
+        match self {
+            Paragraph::Text(parts) => parts_refer_to_footnote(parts, id),
+            Paragraph::List(list) => list.items.iter().any(|item| in_blocks(item)),
+            Paragraph::Code(_) | Paragraph::Rule | Paragraph::RawHtml(_) => false,
+            Paragraph::BlockQuote(contents) | Paragraph::Admonition { contents, .. } => {
+                in_blocks(contents)
+            }
+            Paragraph::Table(table) => table
+                .header
+                .iter()
+                .chain(table.rows.iter().flatten())
+                .any(|cell| parts_refer_to_footnote(cell, id)),
+            Paragraph::DefinitionList(definitions) => definitions.iter().any(|definition| {
+                parts_refer_to_footnote(&definition.term, id) || in_blocks(&definition.details)
+            }),
+        }
     }
+}
 
-    Some(parse_text_inside(maybe_p))
+pub(crate) fn parts_refer_to_footnote(parts: &[TextPart], id: &str) -> bool {
+    parts.iter().any(|part| match part {
+        TextPart::FootnoteReference(reference) => reference.target == id,
+        _ => false,
+    })
 }
 
-pub fn parse_list(maybe_list: NodeRef<Node>) -> Option<Vec<Vec<TextPart>>> {
-    let list = maybe_list.value().as_element()?;
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct List<'a> {
+    pub ordered: bool,
+    pub start: u32,
+    pub items: Vec<Vec<Paragraph<'a>>>,
+}
 
-    if !(list.name() == "ul" || list.name() == "ol") {
-        return None;
+impl List<'_> {
+    pub fn into_owned(self) -> List<'static> {
+        List {
+            ordered: self.ordered,
+            start: self.start,
+            items: self
+                .items
+                .into_iter()
+                .map(|item| item.into_iter().map(Paragraph::into_owned).collect())
+                .collect(),
+        }
     }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table<'a> {
+    pub header: Vec<Vec<TextPart<'a>>>,
+    pub rows: Vec<Vec<Vec<TextPart<'a>>>>,
+    pub alignments: Vec<Alignment>,
+}
+
+impl Table<'_> {
+    pub fn into_owned(self) -> Table<'static> {
+        Table {
+            header: self.header.into_iter().map(into_owned_parts).collect(),
+            rows: self
+                .rows
+                .into_iter()
+                .map(|row| row.into_iter().map(into_owned_parts).collect())
+                .collect(),
+            alignments: self.alignments,
+        }
+    }
+}
 
-    let mut list = vec![];
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Alignment {
+    #[default]
+    None,
+    Left,
+    Center,
+    Right,
+}
+
+impl Alignment {
+    fn from_cell(cell: &Element) -> Alignment {
+        let align = cell.attr("align").or_else(|| {
+            let style = cell.attr("style")?;
+            let (_, rest) = style.split_once("text-align:")?;
+            rest.split(';').next()
+        });
+        match align.map(str::trim) {
+            Some("left") => Alignment::Left,
+            Some("center") => Alignment::Center,
+            Some("right") => Alignment::Right,
+            _ => Alignment::None,
+        }
+    }
+}
 
-    for child in maybe_list.children() {
-        if let Some(li) = child.value().as_element() {
-            if li.name() == "li" {
-                list.push(parse_text_inside(child));
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Definition<'a> {
+    pub term: Vec<TextPart<'a>>,
+    pub details: Vec<Paragraph<'a>>,
+}
+
+impl Definition<'_> {
+    pub fn into_owned(self) -> Definition<'static> {
+        Definition {
+            term: into_owned_parts(self.term),
+            details: self
+                .details
+                .into_iter()
+                .map(Paragraph::into_owned)
+                .collect(),
+        }
+    }
+}
+
+pub fn parse_p(maybe_p: NodeRef<Node>) -> Result<Vec<TextPart>, ParseError> {
+    expect_element(maybe_p, Stage::Paragraph, "p")?;
+
+    Ok(parse_text_inside(maybe_p))
+}
+
+pub fn parse_table(maybe_table: NodeRef<Node>) -> Result<Table, ParseError> {
+    let element = maybe_table
+        .value()
+        .as_element()
+        .ok_or_else(|| ParseError::new(Stage::Table, Expected::Element("table"), maybe_table))?;
+
+    if element.name() == "div" && element.classes().next().is_none() {
+        let mut children = maybe_table
+            .children()
+            .filter(|child| child.value().is_element());
+        if let (Some(table), None) = (children.next(), children.next()) {
+            return parse_table(table);
+        }
+    }
+    expect_element(maybe_table, Stage::Table, "table")?;
+
+    let mut header = vec![];
+    let mut rows = vec![];
+    let mut alignments = vec![];
+
+    // Only rows of this table, not of a table nested in one of its cells
+    let is_element = |node: &NodeRef<Node>, names: &[&str]| {
+        node.value()
+            .as_element()
+            .is_some_and(|element| names.contains(&element.name()))
+    };
+    let table_rows = maybe_table
+        .children()
+        .flat_map(|child| {
+            if is_element(&child, &["thead", "tbody", "tfoot"]) {
+                child.children().collect()
+            } else {
+                vec![child]
+            }
+        })
+        .filter(|row| is_element(row, &["tr"]));
+    for row in table_rows {
+        let cells = row.children().filter_map(|cell| {
+            let element = cell.value().as_element()?;
+            (element.name() == "th" || element.name() == "td").then_some((cell, element))
+        });
+        let in_header = row
+            .parent()
+            .and_then(|parent| parent.value().as_element())
+            .is_some_and(|parent| parent.name() == "thead");
+
+        if in_header && header.is_empty() {
+            for (cell, element) in cells {
+                header.push(parse_text_inside(cell));
+                alignments.push(Alignment::from_cell(element));
+            }
+        } else {
+            let mut columns = vec![];
+            for (cell, element) in cells {
+                if alignments.len() <= columns.len() {
+                    alignments.push(Alignment::from_cell(element));
+                }
+                columns.push(parse_text_inside(cell));
             }
+            rows.push(columns);
         }
     }
 
-    Some(list)
+    Ok(Table {
+        header,
+        rows,
+        alignments,
+    })
 }
 
-pub fn parse_code(maybe_code: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let code = maybe_code.value().as_element()?;
+pub fn parse_rule(maybe_rule: NodeRef<Node>) -> Result<(), ParseError> {
+    expect_element(maybe_rule, Stage::Rule, "hr").map(|_| ())
+}
 
-    if !(code.name() == "div" && code.has_class("example-wrap", CaseSensitivity::CaseSensitive)) {
-        return None;
+pub fn parse_raw_html(maybe_html: NodeRef<Node>) -> Result<Cow<str>, ParseError> {
+    let element = ElementRef::wrap(maybe_html)
+        .ok_or_else(|| ParseError::new(Stage::RawHtml, Expected::Element("div"), maybe_html))?;
+
+    if is_tooltip(element.value()) {
+        return Err(ParseError::new(
+            Stage::RawHtml,
+            Expected::NotClass("information"),
+            maybe_html,
+        ));
+    }
+
+    Ok(Cow::Owned(element.html()))
+}
+
+// Older rustdoc renders the tooltip of a `compile_fail` example before its code block.
+fn is_tooltip(element: &Element) -> bool {
+    element.name() == "div" && element.has_class("information", CaseSensitivity::CaseSensitive)
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CodeBlock<'a> {
+    pub language: Option<Cow<'a, str>>,
+    pub attributes: Vec<CodeAttribute>,
+    pub playground_url: Option<Cow<'a, str>>,
+    pub code: Vec<TextPart<'a>>,
+}
+
+impl CodeBlock<'_> {
+    pub fn into_owned(self) -> CodeBlock<'static> {
+        CodeBlock {
+            language: self.language.map(into_owned),
+            attributes: self.attributes,
+            playground_url: self.playground_url.map(into_owned),
+            code: into_owned_parts(self.code),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum CodeAttribute {
+    Ignore,
+    CompileFail,
+    ShouldPanic,
+    Edition(u16),
+}
+
+impl CodeAttribute {
+    pub fn from_class(class: &str) -> Option<CodeAttribute> {
+        match class {
+            "ignore" => Some(CodeAttribute::Ignore),
+            "compile_fail" => Some(CodeAttribute::CompileFail),
+            "should_panic" => Some(CodeAttribute::ShouldPanic),
+            _ => class
+                .strip_prefix("edition")?
+                .parse()
+                .ok()
+                .map(CodeAttribute::Edition),
+        }
+    }
+}
+
+pub fn parse_code(maybe_code: NodeRef<Node>) -> Result<CodeBlock, ParseError> {
+    if let Ok(pre) = parse_pre(maybe_code) {
+        return Ok(code_block(maybe_code, pre.code, vec![], None));
+    }
+
+    let code = expect_element(maybe_code, Stage::Code, "div")?;
+
+    if !code.has_class("example-wrap", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Code,
+            Expected::Class("example-wrap"),
+            maybe_code,
+        ));
     }
 
+    let attributes: Vec<CodeAttribute> = class_list(code)
+        .filter_map(CodeAttribute::from_class)
+        .collect();
+
     for child in maybe_code.children() {
-        if let Some(code) = parse_pre(child) {
-            return Some(code.code);
+        if let Ok(pre) = parse_pre(child) {
+            let playground_url = maybe_code.descendants().find_map(|link| {
+                let element = link.value().as_element()?;
+                if element.name() == "a"
+                    && element.has_class("test-arrow", CaseSensitivity::CaseSensitive)
+                {
+                    element.attr("href").map(Cow::Borrowed)
+                } else {
+                    None
+                }
+            });
+
+            return Ok(code_block(child, pre.code, attributes, playground_url));
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::Code,
+        Expected::Child("pre"),
+        maybe_code,
+    ))
+}
+
+fn code_block<'a>(
+    pre: NodeRef<'a, Node>,
+    code: Vec<TextPart<'a>>,
+    mut attributes: Vec<CodeAttribute>,
+    playground_url: Option<Cow<'a, str>>,
+) -> CodeBlock<'a> {
+    let mut language = None;
+    for class in pre.value().as_element().into_iter().flat_map(class_list) {
+        if let Some(name) = class.strip_prefix("language-") {
+            language = Some(Cow::Borrowed(name));
+        } else if class == "rust" {
+            language = language.or(Some(Cow::Borrowed(class)));
+        } else if let Some(attribute) = CodeAttribute::from_class(class) {
+            if !attributes.contains(&attribute) {
+                attributes.push(attribute);
+            }
+        }
+    }
+
+    CodeBlock {
+        language,
+        attributes,
+        playground_url,
+        code,
+    }
 }
 
 pub struct Pre<'a> {
     pub code: Vec<TextPart<'a>>,
 }
 
-pub fn parse_pre(maybe_pre: NodeRef<Node>) -> Option<Pre> {
-    let pre = maybe_pre.value().as_element()?;
-    if pre.name() != "pre" {
-        return None;
-    }
+pub fn parse_pre(maybe_pre: NodeRef<Node>) -> Result<Pre, ParseError> {
+    expect_element(maybe_pre, Stage::Pre, "pre")?;
 
-    Some(Pre {
+    Ok(Pre {
         code: parse_text_inside(maybe_pre),
     })
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum TextPart<'a> {
-    Text(&'a str),
-    Image(&'a str),
+    Text(Cow<'a, str>),
+    Image(Image<'a>),
     Table,
+    Token(TokenKind, Cow<'a, str>),
+    FootnoteReference(FootnoteReference<'a>),
     BeginStyle(TextStyle<'a>),
     EndStyle,
 }
 
-#[derive(Debug)]
+impl TextPart<'_> {
+    pub fn into_owned(self) -> TextPart<'static> {
+        match self {
+            TextPart::Text(text) => TextPart::Text(into_owned(text)),
+            TextPart::Image(image) => TextPart::Image(image.into_owned()),
+            TextPart::Table => TextPart::Table,
+            TextPart::Token(kind, text) => TextPart::Token(kind, into_owned(text)),
+            TextPart::FootnoteReference(reference) => {
+                TextPart::FootnoteReference(reference.into_owned())
+            }
+            TextPart::BeginStyle(style) => TextPart::BeginStyle(style.into_owned()),
+            TextPart::EndStyle => TextPart::EndStyle,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Image<'a> {
+    pub src: Cow<'a, str>,
+    pub alt: Option<Cow<'a, str>>,
+    pub title: Option<Cow<'a, str>>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+}
+
+impl Image<'_> {
+    /// Resolves a relative `src` against the URL of the page the image came from.
+    pub fn resolve_src(&self, page_url: &str) -> Cow<'_, str> {
+        resolve_url(page_url, &self.src)
+    }
+
+    pub fn into_owned(self) -> Image<'static> {
+        Image {
+            src: into_owned(self.src),
+            alt: self.alt.map(into_owned),
+            title: self.title.map(into_owned),
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FootnoteReference<'a> {
+    pub id: Option<Cow<'a, str>>,
+    pub target: Cow<'a, str>,
+    pub label: Cow<'a, str>,
+}
+
+impl FootnoteReference<'_> {
+    pub fn into_owned(self) -> FootnoteReference<'static> {
+        FootnoteReference {
+            id: self.id.map(into_owned),
+            target: into_owned(self.target),
+            label: into_owned(self.label),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum TextStyle<'a> {
-    Link(&'a str),
+    Link(Cow<'a, str>),
     Bold,
     Italic,
     Underline,
@@ -88,69 +501,314 @@ pub enum TextStyle<'a> {
     Monospaced,
 }
 
+impl TextStyle<'_> {
+    pub fn into_owned(self) -> TextStyle<'static> {
+        match self {
+            TextStyle::Link(href) => TextStyle::Link(into_owned(href)),
+            TextStyle::Bold => TextStyle::Bold,
+            TextStyle::Italic => TextStyle::Italic,
+            TextStyle::Underline => TextStyle::Underline,
+            TextStyle::Strikethrough => TextStyle::Strikethrough,
+            TextStyle::Monospaced => TextStyle::Monospaced,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TokenKind {
+    Keyword,
+    Keyword2,
+    SelfValue,
+    Ident,
+    Number,
+    String,
+    BoolValue,
+    Op,
+    QuestionMark,
+    Macro,
+    MacroNonterminal,
+    Comment,
+    DocComment,
+    PreludeValue,
+    PreludeType,
+    Lifetime,
+    Attribute,
+}
+
+const TOKEN_CLASSES: &[(&str, TokenKind)] = &[
+    ("kw", TokenKind::Keyword),
+    ("kw-2", TokenKind::Keyword2),
+    ("self", TokenKind::SelfValue),
+    ("ident", TokenKind::Ident),
+    ("number", TokenKind::Number),
+    ("string", TokenKind::String),
+    ("bool-val", TokenKind::BoolValue),
+    ("op", TokenKind::Op),
+    ("question-mark", TokenKind::QuestionMark),
+    ("macro", TokenKind::Macro),
+    ("macro-nonterminal", TokenKind::MacroNonterminal),
+    ("comment", TokenKind::Comment),
+    ("doccomment", TokenKind::DocComment),
+    ("prelude-val", TokenKind::PreludeValue),
+    ("prelude-ty", TokenKind::PreludeType),
+    ("lifetime", TokenKind::Lifetime),
+    ("attr", TokenKind::Attribute),
+];
+
+// Older rustdoc, such as the 1.56 and 1.58 pages in `examples`, spells out `attribute`.
+const LEGACY_TOKEN_CLASSES: &[(&str, TokenKind)] = &[("attribute", TokenKind::Attribute)];
+
+impl TokenKind {
+    pub fn from_class(class: &str) -> Option<TokenKind> {
+        TOKEN_CLASSES
+            .iter()
+            .chain(LEGACY_TOKEN_CLASSES)
+            .find(|(name, _)| *name == class)
+            .map(|(_, kind)| *kind)
+    }
+
+    pub fn class(&self) -> &'static str {
+        TOKEN_CLASSES
+            .iter()
+            .find(|(_, kind)| kind == self)
+            .map(|(name, _)| *name)
+            .unwrap()
+    }
+}
+
+pub(crate) fn into_owned(text: Cow<'_, str>) -> Cow<'static, str> {
+    Cow::Owned(text.into_owned())
+}
+
+pub(crate) fn plain_text(parts: &[TextPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            TextPart::Text(text) | TextPart::Token(_, text) => Some(&**text),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn into_owned_parts(parts: Vec<TextPart<'_>>) -> Vec<TextPart<'static>> {
+    parts.into_iter().map(TextPart::into_owned).collect()
+}
+
+/// A nested view of text. `Document` stores flat `TextPart` streams; build the tree with
+/// `Inline::from_parts` and flatten it back with `Inline::to_parts`, or parse it straight from
+/// the HTML with `parse_inline`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Inline<'a> {
+    Text(Cow<'a, str>),
+    Link {
+        href: Cow<'a, str>,
+        children: Vec<Inline<'a>>,
+    },
+    Emphasis(Vec<Inline<'a>>),
+    Strong(Vec<Inline<'a>>),
+    Code(Vec<Inline<'a>>),
+    Underline(Vec<Inline<'a>>),
+    Strikethrough(Vec<Inline<'a>>),
+    Image(Image<'a>),
+    LineBreak,
+    Table,
+    Token(TokenKind, Cow<'a, str>),
+    FootnoteReference(FootnoteReference<'a>),
+}
+
+impl<'a> Inline<'a> {
+    /// Returns `None` if a `BeginStyle` is never closed or an `EndStyle` has nothing to close,
+    /// since no tree flattens back to such a stream.
+    ///
+    /// A line break is stored as `TextPart::Text("\n")` and comes back as `Inline::Text("\n")`,
+    /// never as `Inline::LineBreak`; only `parse_inline` produces `LineBreak`.
+    pub fn from_parts(parts: &[TextPart<'a>]) -> Option<Vec<Inline<'a>>> {
+        let mut stack: Vec<(&TextStyle<'a>, Vec<Inline<'a>>)> = vec![];
+        let mut current = vec![];
+
+        for part in parts {
+            match part {
+                TextPart::Text(text) => current.push(Inline::Text(text.clone())),
+                TextPart::Image(image) => current.push(Inline::Image(image.clone())),
+                TextPart::Table => current.push(Inline::Table),
+                TextPart::Token(kind, text) => current.push(Inline::Token(*kind, text.clone())),
+                TextPart::FootnoteReference(reference) => {
+                    current.push(Inline::FootnoteReference(reference.clone()))
+                }
+                TextPart::BeginStyle(style) => {
+                    stack.push((style, std::mem::take(&mut current)));
+                }
+                TextPart::EndStyle => {
+                    let (style, parent) = stack.pop()?;
+                    let children = std::mem::replace(&mut current, parent);
+                    current.push(Inline::styled(style, children));
+                }
+            }
+        }
+
+        stack.is_empty().then_some(current)
+    }
+
+    fn styled(style: &TextStyle<'a>, children: Vec<Inline<'a>>) -> Self {
+        match style {
+            TextStyle::Link(href) => Inline::Link {
+                href: href.clone(),
+                children,
+            },
+            TextStyle::Bold => Inline::Strong(children),
+            TextStyle::Italic => Inline::Emphasis(children),
+            TextStyle::Underline => Inline::Underline(children),
+            TextStyle::Strikethrough => Inline::Strikethrough(children),
+            TextStyle::Monospaced => Inline::Code(children),
+        }
+    }
+
+    /// Flattens the tree the way `Document` stores text, so `Inline::LineBreak` becomes
+    /// `TextPart::Text("\n")`. Flattening the result of `from_parts` gives back its input.
+    pub fn to_parts(inlines: &[Inline<'a>]) -> Vec<TextPart<'a>> {
+        let mut buffer = vec![];
+        for inline in inlines {
+            inline.write_parts(&mut buffer);
+        }
+        buffer
+    }
+
+    pub fn write_parts(&self, buffer: &mut Vec<TextPart<'a>>) {
+        let (style, children) = match self {
+            Inline::Text(text) => return buffer.push(TextPart::Text(text.clone())),
+            Inline::LineBreak => return buffer.push(TextPart::Text("\n".into())),
+            Inline::Image(image) => return buffer.push(TextPart::Image(image.clone())),
+            Inline::Table => return buffer.push(TextPart::Table),
+            Inline::Token(kind, text) => return buffer.push(TextPart::Token(*kind, text.clone())),
+            Inline::FootnoteReference(reference) => {
+                return buffer.push(TextPart::FootnoteReference(reference.clone()));
+            }
+            Inline::Link { href, children } => (TextStyle::Link(href.clone()), children),
+            Inline::Strong(children) => (TextStyle::Bold, children),
+            Inline::Emphasis(children) => (TextStyle::Italic, children),
+            Inline::Underline(children) => (TextStyle::Underline, children),
+            Inline::Strikethrough(children) => (TextStyle::Strikethrough, children),
+            Inline::Code(children) => (TextStyle::Monospaced, children),
+        };
+
+        buffer.push(TextPart::BeginStyle(style));
+        for child in children {
+            child.write_parts(buffer);
+        }
+        buffer.push(TextPart::EndStyle);
+    }
+}
+
 pub fn parse_text_outside(node: NodeRef<Node>) -> Vec<TextPart> {
     let mut buffer = vec![];
     parse_text_outside_to(node, &mut buffer);
     buffer
 }
 
-fn parse_text_outside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<TextPart<'a>>) {
+pub fn parse_text_outside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<TextPart<'a>>) {
+    let mut inlines = vec![];
+    parse_inline_outside_to(node, &mut inlines);
+    for inline in &inlines {
+        inline.write_parts(buffer);
+    }
+}
+
+fn parse_inline_outside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<Inline<'a>>) {
     match node.value() {
         Node::Text(text) => {
-            buffer.push(TextPart::Text(text));
+            buffer.push(Inline::Text(Cow::Borrowed(text)));
         }
         Node::Element(element) => match element.name() {
             "a" => {
+                if element.has_class("anchor", CaseSensitivity::CaseSensitive)
+                    || element.has_class("doc-anchor", CaseSensitivity::CaseSensitive)
+                    || element.attr("data-notable-ty").is_some()
+                    || element
+                        .attr("href")
+                        .is_some_and(|href| href.starts_with("#fnref"))
+                {
+                    return;
+                }
                 if let Some(href) = element.attr("href") {
-                    buffer.push(TextPart::BeginStyle(TextStyle::Link(href)));
-                    parse_text_inside_to(node, buffer);
-                    buffer.push(TextPart::EndStyle);
+                    buffer.push(Inline::Link {
+                        href: href.into(),
+                        children: parse_inline_inside(node),
+                    });
+                } else {
+                    // Older rustdoc leaves the `href` off a trait method's name in its code header.
+                    parse_inline_inside_to(node, buffer);
                 }
             }
-            "strong" => {
-                buffer.push(TextPart::BeginStyle(TextStyle::Bold));
-                parse_text_inside_to(node, buffer);
-                buffer.push(TextPart::EndStyle);
+            "strong" | "b" => {
+                buffer.push(Inline::Strong(parse_inline_inside(node)));
             }
-            "em" => {
-                buffer.push(TextPart::BeginStyle(TextStyle::Italic));
-                parse_text_inside_to(node, buffer);
-                buffer.push(TextPart::EndStyle);
+            "em" | "i" => {
+                buffer.push(Inline::Emphasis(parse_inline_inside(node)));
             }
-            "u" => {
-                buffer.push(TextPart::BeginStyle(TextStyle::Underline));
-                parse_text_inside_to(node, buffer);
-                buffer.push(TextPart::EndStyle);
+            "u" | "ins" => {
+                buffer.push(Inline::Underline(parse_inline_inside(node)));
             }
-            "del" => {
-                buffer.push(TextPart::BeginStyle(TextStyle::Strikethrough));
-                parse_text_inside_to(node, buffer);
-                buffer.push(TextPart::EndStyle);
+            "del" | "s" | "strike" => {
+                buffer.push(Inline::Strikethrough(parse_inline_inside(node)));
             }
-            "code" => {
-                buffer.push(TextPart::BeginStyle(TextStyle::Monospaced));
-                parse_text_inside_to(node, buffer);
-                buffer.push(TextPart::EndStyle);
+            "code" | "kbd" | "samp" | "tt" => {
+                buffer.push(Inline::Code(parse_inline_inside(node)));
             }
             "br" => {
-                buffer.push(TextPart::Text("\n"));
+                buffer.push(Inline::LineBreak);
+            }
+            "sup" => {
+                if let Some(reference) = parse_footnote_reference(node) {
+                    buffer.push(Inline::FootnoteReference(reference));
+                } else {
+                    parse_inline_inside_to(node, buffer);
+                }
             }
             "span" => {
+                if element.has_class("notable-traits", CaseSensitivity::CaseSensitive) {
+                    return;
+                }
                 if element.has_class("fmt-newline", CaseSensitivity::CaseSensitive) {
-                    buffer.push(TextPart::Text("\n"));
+                    buffer.push(Inline::LineBreak);
+                }
+                if let Some(token) = parse_token(node) {
+                    buffer.push(token);
+                    return;
                 }
-                parse_text_inside_to(node, buffer);
+                parse_inline_inside_to(node, buffer);
             }
-            "p" => {
-                parse_text_inside_to(node, buffer);
+            "p" | "sub" | "abbr" | "small" | "mark" | "cite" | "q" | "var" | "dfn" | "time" => {
+                parse_inline_inside_to(node, buffer);
+            }
+            "div" if element.has_class("where", CaseSensitivity::CaseSensitive) => {
+                buffer.push(Inline::LineBreak);
+                parse_inline_inside_to(node, buffer);
             }
             "img" => {
                 if let Some(src) = element.attr("src") {
-                    buffer.push(TextPart::Image(src));
+                    buffer.push(Inline::Image(Image {
+                        src: src.into(),
+                        alt: element.attr("alt").map(Cow::Borrowed),
+                        title: element.attr("title").map(Cow::Borrowed),
+                        width: element.attr("width").and_then(|width| width.parse().ok()),
+                        height: element
+                            .attr("height")
+                            .and_then(|height| height.parse().ok()),
+                    }));
                 }
             }
             "table" => {
-                buffer.push(TextPart::Table);
+                buffer.push(Inline::Table);
             }
             _ => {}
         },
@@ -158,96 +816,236 @@ fn parse_text_outside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<TextPart<
     }
 }
 
-pub fn parse_text_inside(node: NodeRef<Node>) -> Vec<TextPart> {
+fn parse_footnote_reference(node: NodeRef<Node>) -> Option<FootnoteReference> {
+    let sup = node.value().as_element()?;
+    let id = sup.id().filter(|id| id.starts_with("fnref"));
+    if id.is_none() && !sup.has_class("footnote-reference", CaseSensitivity::CaseSensitive) {
+        return None;
+    }
+
+    let mut children = node.children().filter(|child| {
+        !child
+            .value()
+            .as_text()
+            .is_some_and(|text| text.trim().is_empty())
+    });
+    let link = ElementRef::wrap(children.next()?)?;
+    if link.value().name() != "a" || children.next().is_some() {
+        return None;
+    }
+
+    let target = link.value().attr("href")?.strip_prefix('#')?;
+    let mut texts = link.text();
+    let label = match (texts.next(), texts.next()) {
+        (Some(label), None) => Cow::Borrowed(label),
+        _ => Cow::Owned(link.text().collect()),
+    };
+
+    Some(FootnoteReference {
+        id: id.map(Cow::Borrowed),
+        target: Cow::Borrowed(target),
+        label,
+    })
+}
+
+fn parse_token(node: NodeRef<Node>) -> Option<Inline> {
+    let element = ElementRef::wrap(node)?;
+    let kind = class_list(element.value()).find_map(TokenKind::from_class)?;
+
+    if node.descendants().any(|child| {
+        child
+            .value()
+            .as_element()
+            .is_some_and(|e| e.name() != "span")
+    }) {
+        return None;
+    }
+
+    let mut texts = element.text();
+    let first = texts.next().unwrap_or_default();
+    let text = match texts.next() {
+        None => Cow::Borrowed(first),
+        Some(second) => {
+            let mut text = first.to_string();
+            text.push_str(second);
+            texts.for_each(|rest| text.push_str(rest));
+            Cow::Owned(text)
+        }
+    };
+
+    Some(Inline::Token(kind, text))
+}
+
+pub fn parse_inline(element: ElementRef) -> Vec<Inline> {
+    parse_inline_inside(*element)
+}
+
+fn parse_inline_inside(node: NodeRef<Node>) -> Vec<Inline> {
     let mut buffer = vec![];
-    parse_text_inside_to(node, &mut buffer);
+    parse_inline_inside_to(node, &mut buffer);
     buffer
 }
 
-pub fn parse_text_inside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<TextPart<'a>>) {
+fn parse_inline_inside_to<'a>(node: NodeRef<'a, Node>, buffer: &mut Vec<Inline<'a>>) {
     for child in node.children() {
-        parse_text_outside_to(child, buffer);
+        parse_inline_outside_to(child, buffer);
+    }
+}
+
+const INLINE_ELEMENTS: &[&str] = &[
+    "a", "strong", "b", "em", "i", "u", "ins", "del", "s", "strike", "code", "kbd", "samp", "tt",
+    "br", "span", "sup", "sub", "abbr", "small", "mark", "cite", "q", "var", "dfn", "time", "img",
+];
+
+pub fn is_inline(node: NodeRef<Node>) -> bool {
+    match node.value() {
+        Node::Text(_) => true,
+        Node::Element(element) => INLINE_ELEMENTS.contains(&element.name()),
+        _ => false,
     }
 }
 
-#[derive(Debug)]
+pub fn parse_text_inside(node: NodeRef<Node>) -> Vec<TextPart> {
+    Inline::to_parts(&parse_inline_inside(node))
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Details<'a> {
     pub summary: Vec<TextPart<'a>>,
     pub detail: Option<Vec<TextPart<'a>>>,
 }
 
-pub fn parse_unstable(maybe_unstable: NodeRef<Node>) -> Option<Details> {
-    let unstable = maybe_unstable.value().as_element()?;
+impl Details<'_> {
+    pub fn into_owned(self) -> Details<'static> {
+        Details {
+            summary: into_owned_parts(self.summary),
+            detail: self.detail.map(into_owned_parts),
+        }
+    }
+}
+
+pub fn parse_unstable(maybe_unstable: NodeRef<Node>) -> Result<Details, ParseError> {
+    let unstable = maybe_unstable.value().as_element().ok_or_else(|| {
+        ParseError::new(Stage::Unstable, Expected::Class("unstable"), maybe_unstable)
+    })?;
 
     if !(unstable.has_class("unstable", CaseSensitivity::CaseSensitive)) {
-        return None;
+        return Err(ParseError::new(
+            Stage::Unstable,
+            Expected::Class("unstable"),
+            maybe_unstable,
+        ));
     }
 
     let result = maybe_unstable
         .first_child()
-        .and_then(parse_details)
+        .and_then(|child| parse_details(child).ok())
         .unwrap_or_else(|| Details {
             summary: parse_text_inside(maybe_unstable),
             detail: None,
         });
-    Some(result)
+    Ok(result)
 }
 
-pub fn parse_portability(maybe_portability: NodeRef<Node>) -> Option<Details> {
-    let portability = maybe_portability.value().as_element()?;
+pub fn parse_portability(maybe_portability: NodeRef<Node>) -> Result<Details, ParseError> {
+    let portability = maybe_portability.value().as_element().ok_or_else(|| {
+        ParseError::new(
+            Stage::Portability,
+            Expected::Class("portability"),
+            maybe_portability,
+        )
+    })?;
 
     if !(portability.has_class("portability", CaseSensitivity::CaseSensitive)) {
-        return None;
+        return Err(ParseError::new(
+            Stage::Portability,
+            Expected::Class("portability"),
+            maybe_portability,
+        ));
     }
 
     let result = maybe_portability
         .first_child()
-        .and_then(parse_details)
+        .and_then(|child| parse_details(child).ok())
         .unwrap_or_else(|| Details {
             summary: parse_text_inside(maybe_portability),
             detail: None,
         });
-    Some(result)
+    Ok(result)
 }
 
-pub fn parse_deprecated(maybe_deprecated: NodeRef<Node>) -> Option<Details> {
-    let deprecated = maybe_deprecated.value().as_element()?;
+pub fn parse_deprecated(maybe_deprecated: NodeRef<Node>) -> Result<Details, ParseError> {
+    let deprecated = maybe_deprecated.value().as_element().ok_or_else(|| {
+        ParseError::new(
+            Stage::Deprecated,
+            Expected::Class("deprecated"),
+            maybe_deprecated,
+        )
+    })?;
 
     if !(deprecated.has_class("deprecated", CaseSensitivity::CaseSensitive)) {
-        return None;
+        return Err(ParseError::new(
+            Stage::Deprecated,
+            Expected::Class("deprecated"),
+            maybe_deprecated,
+        ));
     }
 
     let result = maybe_deprecated
         .first_child()
-        .and_then(parse_details)
+        .and_then(|child| parse_details(child).ok())
         .unwrap_or_else(|| Details {
             summary: parse_text_inside(maybe_deprecated),
             detail: None,
         });
-    Some(result)
+    Ok(result)
 }
 
-fn parse_details(maybe_details: NodeRef<Node>) -> Option<Details> {
-    let details = maybe_details.value().as_element()?;
-
-    if details.name() != "details" {
-        return None;
-    }
+fn parse_details(maybe_details: NodeRef<Node>) -> Result<Details, ParseError> {
+    expect_element(maybe_details, Stage::Details, "details")?;
 
     let mut children = maybe_details.children();
-    let maybe_summary = children.next()?;
-    let summary = maybe_summary.value().as_element()?;
-
-    if summary.name() != "summary" {
-        return None;
-    }
+    let maybe_summary = children.next().ok_or_else(|| {
+        ParseError::new(Stage::Details, Expected::Child("summary"), maybe_details)
+    })?;
+    expect_element(maybe_summary, Stage::Details, "summary")?;
     let summary = parse_text_inside(maybe_summary);
 
     let mut detail = vec![];
     children.for_each(|child| parse_text_outside_to(child, &mut detail));
 
-    Some(Details {
+    Ok(Details {
         summary,
         detail: Some(detail),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use scraper::Html;
+
+    use super::parse_raw_html;
+    use crate::error::{Expected, Stage};
+
+    #[test]
+    fn tooltips_are_not_raw_html() {
+        let html = Html::parse_fragment(
+            "<div class=\"information\"><div class=\"tooltip\">ⓘ</div></div><div class=\"other\"></div>",
+        );
+        let mut divs = html.root_element().children();
+
+        let error = parse_raw_html(divs.next().unwrap()).unwrap_err();
+        assert_eq!(error.stage, Stage::RawHtml);
+        assert_eq!(error.expected, Expected::NotClass("information"));
+        assert_eq!(
+            error.to_string(),
+            "raw html: unexpected class `information` at html:nth-child(1) > div.information:nth-child(1)"
+        );
+
+        assert_eq!(
+            parse_raw_html(divs.next().unwrap()).unwrap(),
+            "<div class=\"other\"></div>"
+        );
+    }
+}
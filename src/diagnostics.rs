@@ -0,0 +1,48 @@
+use ego_tree::NodeRef;
+use scraper::Node;
+
+use crate::error::{class_list, element_position, NodePath, Stage};
+
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    /// Elements the parser did not understand. Doc block elements kept verbatim as
+    /// `Paragraph::RawHtml` are listed as well, under `Stage::RawHtml`.
+    pub skipped: Vec<SkippedNode>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    pub(crate) fn skip(&mut self, stage: Stage, node: NodeRef<Node>) {
+        if let Some(element) = node.value().as_element() {
+            self.skipped.push(SkippedNode {
+                stage,
+                tag: element.name().to_string(),
+                classes: class_list(element).map(str::to_string).collect(),
+                position: element_position(node),
+                path: NodePath::of(node),
+            });
+        }
+    }
+
+    pub(crate) fn append(&mut self, other: &mut Diagnostics) {
+        self.skipped.append(&mut other.skipped);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkippedNode {
+    pub stage: Stage,
+    pub tag: String,
+    pub classes: Vec<String>,
+    /// 1-based index among the element's siblings, as in the `:nth-child` of `path` (which
+    /// leaves it out for elements with an `id`).
+    pub position: usize,
+    pub path: NodePath,
+}
@@ -0,0 +1,142 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    atom::{plain_text, CodeAttribute, CodeBlock, Paragraph},
+    header::Section,
+    Document, ListingType,
+};
+
+impl CodeBlock<'_> {
+    /// The full doctest, hidden lines included, decoded from the playground link. Examples
+    /// without one fall back to their visible text.
+    pub fn doctest_source(&self) -> String {
+        self.playground_url
+            .as_deref()
+            .and_then(playground_code)
+            .unwrap_or_else(|| plain_text(&self.code))
+    }
+
+    pub fn is_rust(&self) -> bool {
+        self.language
+            .as_deref()
+            .is_none_or(|language| language == "rust")
+    }
+
+    pub fn is_runnable(&self) -> bool {
+        self.is_rust()
+            && !self.attributes.iter().any(|attribute| {
+                matches!(
+                    attribute,
+                    CodeAttribute::Ignore | CodeAttribute::CompileFail | CodeAttribute::ShouldPanic
+                )
+            })
+    }
+}
+
+fn playground_code(url: &str) -> Option<String> {
+    let (_, query) = url.split_once('?')?;
+    let code = query
+        .split('&')
+        .find_map(|param| param.strip_prefix("code="))?;
+    percent_decode(code)
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+impl<'a> Document<'a> {
+    pub fn code_blocks(&self) -> Vec<&CodeBlock<'a>> {
+        let mut blocks = vec![];
+        collect_code_blocks(&self.description, &mut blocks);
+        for listing in &self.items {
+            let items = match &listing.kind {
+                ListingType::Table(_) => continue,
+                ListingType::Fields(items) => items.iter().collect::<Vec<_>>(),
+                ListingType::Impls(impls) => impls.iter().flat_map(|i| &i.items).collect(),
+            };
+            for item in items {
+                if let Some(description) = &item.description {
+                    collect_code_blocks(description, &mut blocks);
+                }
+            }
+        }
+        blocks
+    }
+}
+
+fn collect_code_blocks<'d, 'a>(sections: &'d [Section<'a>], blocks: &mut Vec<&'d CodeBlock<'a>>) {
+    for section in sections {
+        collect_paragraph_code_blocks(&section.contents, blocks);
+        for footnote in &section.footnotes {
+            collect_paragraph_code_blocks(&footnote.contents, blocks);
+        }
+    }
+}
+
+fn collect_paragraph_code_blocks<'d, 'a>(
+    paragraphs: &'d [Paragraph<'a>],
+    blocks: &mut Vec<&'d CodeBlock<'a>>,
+) {
+    for paragraph in paragraphs {
+        match paragraph {
+            Paragraph::Code(block) => blocks.push(block),
+            Paragraph::BlockQuote(contents) | Paragraph::Admonition { contents, .. } => {
+                collect_paragraph_code_blocks(contents, blocks)
+            }
+            Paragraph::List(list) => {
+                for item in &list.items {
+                    collect_paragraph_code_blocks(item, blocks);
+                }
+            }
+            Paragraph::DefinitionList(definitions) => {
+                for definition in definitions {
+                    collect_paragraph_code_blocks(&definition.details, blocks);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Writes every runnable Rust example to `dir` as a standalone file named after the item's path,
+/// e.g. `std__option__Option_0.rs`, and returns the paths written. Examples marked `ignore`,
+/// `compile_fail` or `should_panic` are skipped.
+pub fn write_doctests(document: &Document, dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let name = document
+        .path()
+        .map(|path| path.to_string().replace("::", "__"))
+        .unwrap_or_else(|| "example".to_string());
+
+    let mut written = vec![];
+    for (index, block) in document
+        .code_blocks()
+        .into_iter()
+        .filter(|block| block.is_runnable())
+        .enumerate()
+    {
+        let file = dir.join(format!("{}_{}.rs", name, index));
+        fs::write(&file, block.doctest_source())?;
+        written.push(file);
+    }
+
+    Ok(written)
+}
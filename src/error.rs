@@ -0,0 +1,238 @@
+use std::fmt::{self, Write};
+
+use ego_tree::NodeRef;
+use scraper::{node::Element, Node};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub stage: Stage,
+    pub expected: Expected,
+    pub path: NodePath,
+}
+
+impl ParseError {
+    pub(crate) fn new(stage: Stage, expected: Expected, node: NodeRef<Node>) -> Self {
+        Self {
+            stage,
+            expected,
+            path: NodePath::of(node),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} at {}", self.stage, self.expected, self.path)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stage {
+    Main,
+    Fqn,
+    MainHeading,
+    InBand,
+    OutOfBand,
+    ItemDecl,
+    ItemInfo,
+    TopDoc,
+    DocBlock,
+    Paragraph,
+    List,
+    Table,
+    Rule,
+    BlockQuote,
+    DefinitionList,
+    Footnotes,
+    Admonition,
+    RawHtml,
+    Code,
+    Pre,
+    Unstable,
+    Portability,
+    Deprecated,
+    Details,
+    ItemHeader,
+    Field,
+    ItemTable,
+    ItemRow,
+    ItemLeft,
+    ItemRight,
+    BlockTable,
+    ImplHeading,
+    EmptyImpl,
+    ImplItems,
+    TraitItems,
+    ItemHeading,
+    ImplDiv,
+    Implementor,
+    Srclink,
+    ToggleItem,
+    Sidebar,
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Stage::Main => "main",
+            Stage::Fqn => "fqn",
+            Stage::MainHeading => "main heading",
+            Stage::InBand => "in-band",
+            Stage::OutOfBand => "out-of-band",
+            Stage::ItemDecl => "item declaration",
+            Stage::ItemInfo => "item info",
+            Stage::TopDoc => "top doc",
+            Stage::DocBlock => "doc block",
+            Stage::Paragraph => "paragraph",
+            Stage::List => "list",
+            Stage::Table => "table",
+            Stage::Rule => "rule",
+            Stage::BlockQuote => "block quote",
+            Stage::DefinitionList => "definition list",
+            Stage::Footnotes => "footnotes",
+            Stage::Admonition => "admonition",
+            Stage::RawHtml => "raw html",
+            Stage::Code => "code",
+            Stage::Pre => "pre",
+            Stage::Unstable => "unstable",
+            Stage::Portability => "portability",
+            Stage::Deprecated => "deprecated",
+            Stage::Details => "details",
+            Stage::ItemHeader => "item header",
+            Stage::Field => "field",
+            Stage::ItemTable => "item table",
+            Stage::ItemRow => "item row",
+            Stage::ItemLeft => "item left",
+            Stage::ItemRight => "item right",
+            Stage::BlockTable => "block table",
+            Stage::ImplHeading => "impl heading",
+            Stage::EmptyImpl => "empty impl",
+            Stage::ImplItems => "impl items",
+            Stage::TraitItems => "trait items",
+            Stage::ItemHeading => "item heading",
+            Stage::ImplDiv => "impl div",
+            Stage::Implementor => "implementor",
+            Stage::Srclink => "srclink",
+            Stage::ToggleItem => "toggle item",
+            Stage::Sidebar => "sidebar",
+        };
+        f.write_str(name)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Expected {
+    Element(&'static str),
+    AnyElement(&'static [&'static str]),
+    Class(&'static str),
+    AnyClass(&'static [&'static str]),
+    NotClass(&'static str),
+    Attribute(&'static str, &'static str),
+    Child(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Element(name) => write!(f, "expected element `{}`", name),
+            Expected::AnyElement(names) => {
+                f.write_str("expected one of the elements ")?;
+                write_names(f, names)
+            }
+            Expected::Class(class) => write!(f, "expected class `{}`", class),
+            Expected::AnyClass(classes) => {
+                f.write_str("expected one of the classes ")?;
+                write_names(f, classes)
+            }
+            Expected::NotClass(class) => write!(f, "unexpected class `{}`", class),
+            Expected::Attribute(name, value) => {
+                write!(f, "expected attribute `{}=\"{}\"`", name, value)
+            }
+            Expected::Child(selector) => write!(f, "expected child `{}`", selector),
+        }
+    }
+}
+
+fn write_names(f: &mut fmt::Formatter<'_>, names: &[&str]) -> fmt::Result {
+    for (index, name) in names.iter().enumerate() {
+        if index > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "`{}`", name)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodePath(Vec<String>);
+
+impl NodePath {
+    pub(crate) fn of(node: NodeRef<Node>) -> Self {
+        let mut segments: Vec<_> = std::iter::once(node)
+            .chain(node.ancestors())
+            .filter_map(path_segment)
+            .collect();
+        segments.reverse();
+        NodePath(segments)
+    }
+
+    pub fn segments(&self) -> &[String] {
+        &self.0
+    }
+}
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("document");
+        }
+        f.write_str(&self.0.join(" > "))
+    }
+}
+
+fn path_segment(node: NodeRef<Node>) -> Option<String> {
+    match node.value() {
+        Node::Element(element) => {
+            let mut segment = element.name().to_string();
+            if let Some(id) = element.id() {
+                let _ = write!(segment, "#{}", id);
+            }
+            for class in class_list(element) {
+                let _ = write!(segment, ".{}", class);
+            }
+            if element.id().is_none() {
+                let _ = write!(segment, ":nth-child({})", element_position(node));
+            }
+            Some(segment)
+        }
+        Node::Text(_) => Some("#text".to_string()),
+        Node::Comment(_) => Some("#comment".to_string()),
+        _ => None,
+    }
+}
+
+// 1-based, like `:nth-child`.
+pub(crate) fn element_position(node: NodeRef<Node>) -> usize {
+    node.prev_siblings()
+        .filter(|sibling| sibling.value().is_element())
+        .count()
+        + 1
+}
+
+// `Element::classes` iterates a set, so read the attribute to keep the source order.
+pub(crate) fn class_list(element: &Element) -> impl Iterator<Item = &str> {
+    element.attr("class").unwrap_or_default().split_whitespace()
+}
+
+pub(crate) fn expect_element<'a>(
+    node: NodeRef<'a, Node>,
+    stage: Stage,
+    name: &'static str,
+) -> Result<&'a Element, ParseError> {
+    match node.value().as_element() {
+        Some(element) if element.name() == name => Ok(element),
+        _ => Err(ParseError::new(stage, Expected::Element(name), node)),
+    }
+}
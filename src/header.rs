@@ -1,35 +1,158 @@
+use std::borrow::Cow;
+
 use ego_tree::NodeRef;
 use scraper::{ElementRef, Node};
 use selectors::attr::CaseSensitivity;
 
-use crate::atom::{
-    parse_code, parse_deprecated, parse_list, parse_p, parse_portability, parse_pre,
-    parse_text_inside, parse_unstable, Details, Paragraph, TextPart,
+use crate::{
+    anchor::parse_anchor,
+    atom::{
+        into_owned, into_owned_parts, is_inline, parse_code, parse_deprecated, parse_p,
+        parse_portability, parse_pre, parse_raw_html, parse_rule, parse_table, parse_text_inside,
+        parse_text_outside_to, parse_unstable, parts_refer_to_footnote, Definition, Details, List,
+        Paragraph, TextPart,
+    },
+    diagnostics::Diagnostics,
+    error::{class_list, expect_element, Expected, ParseError, Stage},
+    profile::{HeadingLayout, Profile},
+    source::{find_source_link, SourceLocation},
 };
 
 pub struct Fqn<'a> {
     pub title: Vec<TextPart<'a>>,
     pub since: Option<&'a str>,
+    pub source: Option<SourceLocation<'a>>,
+}
+
+pub fn parse_heading<'a>(
+    maybe_heading: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<Fqn<'a>, ParseError> {
+    match profile.heading {
+        HeadingLayout::Fqn => parse_fqn(maybe_heading, profile),
+        HeadingLayout::MainHeading => parse_main_heading(maybe_heading, profile),
+    }
 }
 
-pub fn parse_fqn(maybe_fqn: NodeRef<Node>) -> Option<Fqn> {
-    let fqn = maybe_fqn.value().as_element()?;
+pub fn parse_main_heading<'a>(
+    maybe_main_heading: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<Fqn<'a>, ParseError> {
+    let main_heading = expect_element(maybe_main_heading, Stage::MainHeading, "div")?;
+
+    if !main_heading.has_class("main-heading", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::MainHeading,
+            Expected::Class("main-heading"),
+            maybe_main_heading,
+        ));
+    }
+
+    let mut breadcrumbs = None;
+    let mut h1 = None;
+    let mut out_of_band = None;
 
-    if !(fqn.name() == "h1" && fqn.has_class("fqn", CaseSensitivity::CaseSensitive)) {
-        return None;
+    for child in maybe_main_heading.children() {
+        if let Some(element) = child.value().as_element() {
+            if element.name() == "h1" {
+                h1 = h1.or(Some(child));
+            } else if element.name() == "div"
+                && element.has_class("rustdoc-breadcrumbs", CaseSensitivity::CaseSensitive)
+            {
+                breadcrumbs = breadcrumbs.or_else(|| Some(parse_text_inside(child)));
+            }
+        }
+        out_of_band = out_of_band.or_else(|| {
+            profile
+                .fallbacks()
+                .find_map(|profile| parse_out_of_band(child, &profile).ok())
+        });
+    }
+
+    let h1 = h1.ok_or_else(|| {
+        ParseError::new(
+            Stage::MainHeading,
+            Expected::Child("h1"),
+            maybe_main_heading,
+        )
+    })?;
+
+    let in_band = h1
+        .children()
+        .find_map(|child| parse_in_band(child, profile).ok());
+    let title = match in_band {
+        Some(in_band) => in_band.text,
+        None => {
+            let mut title = vec![];
+            let mut children = h1.children().peekable();
+            while let Some(child) = children.next_if(|child| child.value().is_text()) {
+                parse_text_outside_to(child, &mut title);
+            }
+            if let Some(breadcrumbs) = breadcrumbs {
+                title.extend(breadcrumbs);
+                title.push(TextPart::Text("::".into()));
+            }
+            for child in children {
+                parse_text_outside_to(child, &mut title);
+            }
+            while let Some(TextPart::Text(text)) = title.last() {
+                if !text.trim().is_empty() {
+                    break;
+                }
+                title.pop();
+            }
+            title
+        }
+    };
+
+    let (since, source) = match out_of_band {
+        Some(out_of_band) => (out_of_band.since, out_of_band.source),
+        None => (None, None),
+    };
+
+    Ok(Fqn {
+        title,
+        since,
+        source,
+    })
+}
+
+pub fn parse_fqn<'a>(
+    maybe_fqn: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<Fqn<'a>, ParseError> {
+    let fqn = expect_element(maybe_fqn, Stage::Fqn, "h1")?;
+
+    if !fqn.has_class("fqn", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Fqn,
+            Expected::Class("fqn"),
+            maybe_fqn,
+        ));
     }
 
     let mut in_band = None;
     let mut out_of_band = None;
 
     for child in maybe_fqn.children() {
-        in_band = in_band.or_else(|| parse_in_band(child));
-        out_of_band = out_of_band.or_else(|| parse_out_of_band(child));
+        in_band = in_band.or_else(|| parse_in_band(child, profile).ok());
+        out_of_band = out_of_band.or_else(|| {
+            profile
+                .fallbacks()
+                .find_map(|profile| parse_out_of_band(child, &profile).ok())
+        });
     }
 
-    Some(Fqn {
-        title: in_band?.text,
-        since: out_of_band?.since,
+    let in_band = in_band
+        .ok_or_else(|| ParseError::new(Stage::Fqn, Expected::Child(profile.in_band), maybe_fqn))?;
+    let out_of_band = out_of_band.ok_or_else(|| {
+        ParseError::new(Stage::Fqn, Expected::Child(profile.out_of_band), maybe_fqn)
+    })?;
+
+    Ok(Fqn {
+        title: in_band.text,
+        since: out_of_band.since,
+        source: out_of_band.source,
     })
 }
 
@@ -37,29 +160,42 @@ struct InBand<'a> {
     text: Vec<TextPart<'a>>,
 }
 
-fn parse_in_band(maybe_in_band: NodeRef<Node>) -> Option<InBand> {
-    let in_band = maybe_in_band.value().as_element()?;
-
-    if !(in_band.name() == "span" && in_band.has_class("in-band", CaseSensitivity::CaseSensitive)) {
-        return None;
+fn parse_in_band<'a>(
+    maybe_in_band: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<InBand<'a>, ParseError> {
+    let in_band = expect_element(maybe_in_band, Stage::InBand, "span")?;
+
+    if !in_band.has_class(profile.in_band, CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::InBand,
+            Expected::Class(profile.in_band),
+            maybe_in_band,
+        ));
     }
 
     let text = parse_text_inside(maybe_in_band);
 
-    Some(InBand { text })
+    Ok(InBand { text })
 }
 
 struct OutOfBand<'a> {
     since: Option<&'a str>,
+    source: Option<SourceLocation<'a>>,
 }
 
-fn parse_out_of_band(maybe_out_of_band: NodeRef<Node>) -> Option<OutOfBand> {
-    let out_of_band = maybe_out_of_band.value().as_element()?;
-
-    if !(out_of_band.name() == "span"
-        && out_of_band.has_class("out-of-band", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+fn parse_out_of_band<'a>(
+    maybe_out_of_band: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<OutOfBand<'a>, ParseError> {
+    let out_of_band = expect_element(maybe_out_of_band, Stage::OutOfBand, "span")?;
+
+    if !out_of_band.has_class(profile.out_of_band, CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::OutOfBand,
+            Expected::Class(profile.out_of_band),
+            maybe_out_of_band,
+        ));
     }
 
     for child in maybe_out_of_band.children() {
@@ -67,63 +203,106 @@ fn parse_out_of_band(maybe_out_of_band: NodeRef<Node>) -> Option<OutOfBand> {
             if element.name() == "span"
                 && element.has_class("since", CaseSensitivity::CaseSensitive)
             {
-                let since = ElementRef::wrap(child).unwrap().text().next()?;
-                return Some(OutOfBand { since: Some(since) });
+                let since = ElementRef::wrap(child)
+                    .unwrap()
+                    .text()
+                    .next()
+                    .ok_or_else(|| {
+                        ParseError::new(Stage::OutOfBand, Expected::Child("#text"), child)
+                    })?;
+                return Ok(OutOfBand {
+                    since: Some(since),
+                    source: find_source_link(maybe_out_of_band),
+                });
             }
         }
     }
 
-    Some(OutOfBand { since: None })
+    Ok(OutOfBand {
+        since: None,
+        source: find_source_link(maybe_out_of_band),
+    })
 }
 
 pub struct ItemDecl<'a> {
     pub code: Vec<TextPart<'a>>,
 }
 
-pub fn parse_item_decl(maybe_item_decl: NodeRef<Node>) -> Option<ItemDecl> {
-    let item_decl = maybe_item_decl.value().as_element()?;
-
-    if !(item_decl.name() == "div"
-        && (item_decl.has_class("item-decl", CaseSensitivity::CaseSensitive)
-            || item_decl.has_class("type-decl", CaseSensitivity::CaseSensitive)))
-    {
-        return None;
+pub fn parse_item_decl<'a>(
+    maybe_item_decl: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<ItemDecl<'a>, ParseError> {
+    let item_decl = expect_element(maybe_item_decl, Stage::ItemDecl, "div")?;
+
+    if !item_decl.has_class(profile.item_decl, CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::ItemDecl,
+            Expected::Class(profile.item_decl),
+            maybe_item_decl,
+        ));
     }
 
     for child in maybe_item_decl.children() {
-        if let Some(pre) = parse_pre(child) {
-            return Some(ItemDecl { code: pre.code });
+        if let Ok(pre) = parse_pre(child) {
+            return Ok(ItemDecl { code: pre.code });
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::ItemDecl,
+        Expected::Child("pre"),
+        maybe_item_decl,
+    ))
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemInfo<'a> {
     pub stability: Option<Details<'a>>,
     pub portability: Option<Details<'a>>,
     pub deprecation: Option<Details<'a>>,
 }
 
-pub fn parse_item_info(maybe_item_info: NodeRef<Node>) -> Option<ItemInfo> {
-    let item_info = maybe_item_info.value().as_element()?;
+impl ItemInfo<'_> {
+    pub fn into_owned(self) -> ItemInfo<'static> {
+        ItemInfo {
+            stability: self.stability.map(Details::into_owned),
+            portability: self.portability.map(Details::into_owned),
+            deprecation: self.deprecation.map(Details::into_owned),
+        }
+    }
+}
 
-    if !(item_info.name() == "div"
-        && item_info.has_class("item-info", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_item_info(maybe_item_info: NodeRef<Node>) -> Result<ItemInfo, ParseError> {
+    let item_info = maybe_item_info
+        .value()
+        .as_element()
+        .filter(|item_info| item_info.name() == "div" || item_info.name() == "span")
+        .ok_or_else(|| {
+            ParseError::new(
+                Stage::ItemInfo,
+                Expected::AnyElement(&["div", "span"]),
+                maybe_item_info,
+            )
+        })?;
+
+    if !item_info.has_class("item-info", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::ItemInfo,
+            Expected::Class("item-info"),
+            maybe_item_info,
+        ));
     }
 
     let mut stability = None;
     let mut portability = None;
     let mut deprecation = None;
     for child in maybe_item_info.children() {
-        stability = stability.or_else(|| parse_unstable(child));
-        portability = portability.or_else(|| parse_portability(child));
-        deprecation = deprecation.or_else(|| parse_deprecated(child));
+        stability = stability.or_else(|| parse_unstable(child).ok());
+        portability = portability.or_else(|| parse_portability(child).ok());
+        deprecation = deprecation.or_else(|| parse_deprecated(child).ok());
     }
 
-    Some(ItemInfo {
+    Ok(ItemInfo {
         stability,
         portability,
         deprecation,
@@ -134,46 +313,117 @@ pub struct TopDoc<'a> {
     pub doc_block: DocBlock<'a>,
 }
 
-pub fn parse_top_doc(maybe_top_doc: NodeRef<Node>) -> Option<TopDoc> {
-    let top_doc = maybe_top_doc.value().as_element()?;
-
-    if !(top_doc.name() == "details"
-        && top_doc.has_class("top-doc", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_top_doc<'a>(
+    maybe_top_doc: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<TopDoc<'a>, ParseError> {
+    let top_doc = expect_element(maybe_top_doc, Stage::TopDoc, "details")?;
+
+    if !top_doc.has_class("top-doc", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::TopDoc,
+            Expected::Class("top-doc"),
+            maybe_top_doc,
+        ));
     }
 
     for child in maybe_top_doc.children() {
-        if let Some(doc_block) = parse_doc_block(child) {
-            return Some(TopDoc { doc_block });
+        if let Ok(doc_block) = parse_doc_block(child, diagnostics) {
+            return Ok(TopDoc { doc_block });
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::TopDoc,
+        Expected::Child("div.docblock"),
+        maybe_top_doc,
+    ))
 }
 
 pub struct DocBlock<'a> {
     pub sections: Vec<Section<'a>>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Section<'a> {
     pub depth: u8,
     pub heading: Option<Vec<TextPart<'a>>>,
     pub contents: Vec<Paragraph<'a>>,
+    pub anchor: Option<Cow<'a, str>>,
+    pub footnotes: Vec<Footnote<'a>>,
+}
+
+impl Section<'_> {
+    pub fn into_owned(self) -> Section<'static> {
+        Section {
+            depth: self.depth,
+            heading: self.heading.map(into_owned_parts),
+            contents: self
+                .contents
+                .into_iter()
+                .map(Paragraph::into_owned)
+                .collect(),
+            anchor: self.anchor.map(into_owned),
+            footnotes: self
+                .footnotes
+                .into_iter()
+                .map(Footnote::into_owned)
+                .collect(),
+        }
+    }
+}
+
+/// A footnote definition, kept in the first section that refers to it.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Footnote<'a> {
+    pub id: Cow<'a, str>,
+    pub label: Cow<'a, str>,
+    pub contents: Vec<Paragraph<'a>>,
+    /// The ids of the references pointing back to this definition.
+    pub references: Vec<Cow<'a, str>>,
 }
 
-pub fn parse_doc_block(maybe_doc_block: NodeRef<Node>) -> Option<DocBlock> {
-    let doc_block = maybe_doc_block.value().as_element()?;
+impl Footnote<'_> {
+    pub fn into_owned(self) -> Footnote<'static> {
+        Footnote {
+            id: into_owned(self.id),
+            label: into_owned(self.label),
+            contents: self
+                .contents
+                .into_iter()
+                .map(Paragraph::into_owned)
+                .collect(),
+            references: self.references.into_iter().map(into_owned).collect(),
+        }
+    }
+}
 
-    if !(doc_block.name() == "div"
-        && doc_block.has_class("docblock", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_doc_block<'a>(
+    maybe_doc_block: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<DocBlock<'a>, ParseError> {
+    let doc_block = expect_element(maybe_doc_block, Stage::DocBlock, "div")?;
+
+    if !doc_block.has_class("docblock", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::DocBlock,
+            Expected::Class("docblock"),
+            maybe_doc_block,
+        ));
     }
 
     let mut sections = vec![];
+    let mut footnotes = vec![];
+    let mut inline = vec![];
 
     for child in maybe_doc_block.children() {
+        if is_inline(child) {
+            parse_text_outside_to(child, &mut inline);
+            continue;
+        }
+        flush_inline(&mut sections, &mut inline);
+
         if let Some(element) = child.value().as_element() {
             if element.name().starts_with('h')
                 && (b'1'..=b'6').contains(&element.name().as_bytes()[1])
@@ -184,26 +434,305 @@ pub fn parse_doc_block(maybe_doc_block: NodeRef<Node>) -> Option<DocBlock> {
                     depth,
                     heading: Some(heading),
                     contents: vec![],
+                    anchor: parse_anchor(child),
+                    footnotes: vec![],
                 });
+            } else if let Ok(list) = parse_footnotes(child, diagnostics) {
+                footnotes.extend(list);
+            } else if let Ok(content) = parse_paragraph(child, diagnostics) {
+                push_content(&mut sections, content);
             } else {
-                let content = parse_p(child)
-                    .map(Paragraph::Text)
-                    .or_else(|| parse_list(child).map(Paragraph::List))
-                    .or_else(|| parse_code(child).map(Paragraph::Code));
-                if let Some(content) = content {
-                    if let Some(section) = sections.last_mut() {
-                        section.contents.push(content);
-                    } else {
-                        sections.push(Section {
-                            depth: 0,
-                            heading: None,
-                            contents: vec![content],
-                        });
-                    }
+                diagnostics.skip(Stage::DocBlock, child);
+            }
+        }
+    }
+    flush_inline(&mut sections, &mut inline);
+
+    // Rustdoc renders every definition at the end of the doc block; keep each one next to
+    // the first section that refers to it.
+    for footnote in footnotes {
+        let referring = sections.iter().position(|section| {
+            section
+                .heading
+                .as_deref()
+                .is_some_and(|heading| parts_refer_to_footnote(heading, &footnote.id))
+                || section
+                    .contents
+                    .iter()
+                    .any(|paragraph| paragraph.refers_to_footnote(&footnote.id))
+        });
+        match referring {
+            Some(index) => sections[index].footnotes.push(footnote),
+            None => last_section(&mut sections).footnotes.push(footnote),
+        }
+    }
+
+    Ok(DocBlock { sections })
+}
+
+fn parse_paragraph<'a>(
+    maybe_paragraph: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Paragraph<'a>, ParseError> {
+    parse_p(maybe_paragraph)
+        .map(Paragraph::Text)
+        .or_else(|_| parse_list(maybe_paragraph, diagnostics).map(Paragraph::List))
+        .or_else(|_| parse_code(maybe_paragraph).map(Paragraph::Code))
+        .or_else(|_| parse_table(maybe_paragraph).map(Paragraph::Table))
+        .or_else(|_| parse_rule(maybe_paragraph).map(|_| Paragraph::Rule))
+        .or_else(|_| parse_block_quote(maybe_paragraph, diagnostics).map(Paragraph::BlockQuote))
+        .or_else(|_| {
+            parse_definition_list(maybe_paragraph, diagnostics).map(Paragraph::DefinitionList)
+        })
+        .or_else(|_| parse_admonition(maybe_paragraph, diagnostics))
+        .or_else(|_| {
+            let html = parse_raw_html(maybe_paragraph)?;
+            diagnostics.skip(Stage::RawHtml, maybe_paragraph);
+            Ok(Paragraph::RawHtml(html))
+        })
+}
+
+const ADMONITION_CLASSES: &[&str] = &[
+    "warning",
+    "note",
+    "info",
+    "tip",
+    "important",
+    "caution",
+    "danger",
+];
+
+fn parse_admonition<'a>(
+    maybe_admonition: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Paragraph<'a>, ParseError> {
+    let admonition = expect_element(maybe_admonition, Stage::Admonition, "div")?;
+
+    match class_list(admonition).find(|class| ADMONITION_CLASSES.contains(class)) {
+        Some(kind) => Ok(Paragraph::Admonition {
+            kind: Cow::Borrowed(kind),
+            contents: parse_blocks(maybe_admonition, diagnostics),
+        }),
+        None => Err(ParseError::new(
+            Stage::Admonition,
+            Expected::AnyClass(ADMONITION_CLASSES),
+            maybe_admonition,
+        )),
+    }
+}
+
+fn parse_list<'a>(
+    maybe_list: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<List<'a>, ParseError> {
+    let list = maybe_list
+        .value()
+        .as_element()
+        .ok_or_else(|| ParseError::new(Stage::List, Expected::Element("ul"), maybe_list))?;
+
+    if !(list.name() == "ul" || list.name() == "ol") {
+        return Err(ParseError::new(
+            Stage::List,
+            Expected::Element("ul"),
+            maybe_list,
+        ));
+    }
+
+    let ordered = list.name() == "ol";
+    let start = list
+        .attr("start")
+        .and_then(|start| start.parse().ok())
+        .unwrap_or(1);
+    let mut items = vec![];
+
+    for child in maybe_list.children() {
+        if let Some(li) = child.value().as_element() {
+            if li.name() == "li" {
+                items.push(parse_blocks(child, diagnostics));
+            }
+        }
+    }
+
+    Ok(List {
+        ordered,
+        start,
+        items,
+    })
+}
+
+fn parse_block_quote<'a>(
+    maybe_block_quote: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Paragraph<'a>>, ParseError> {
+    expect_element(maybe_block_quote, Stage::BlockQuote, "blockquote")?;
+
+    Ok(parse_blocks(maybe_block_quote, diagnostics))
+}
+
+fn parse_definition_list<'a>(
+    maybe_definition_list: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Definition<'a>>, ParseError> {
+    expect_element(maybe_definition_list, Stage::DefinitionList, "dl")?;
+
+    let mut definitions: Vec<Definition> = vec![];
+    for child in maybe_definition_list.children() {
+        match child.value().as_element().map(|element| element.name()) {
+            Some("dt") => definitions.push(Definition {
+                term: parse_text_inside(child),
+                details: vec![],
+            }),
+            Some("dd") => {
+                if let Some(last_definition) = definitions.last_mut() {
+                    last_definition
+                        .details
+                        .extend(parse_blocks(child, diagnostics));
                 }
             }
+            _ => {}
+        }
+    }
+    Ok(definitions)
+}
+
+fn parse_blocks<'a>(
+    parent: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Vec<Paragraph<'a>> {
+    let mut contents = vec![];
+    let mut inline = vec![];
+
+    for child in parent.children() {
+        if is_inline(child) {
+            parse_text_outside_to(child, &mut inline);
+            continue;
+        }
+        contents.extend(take_inline(&mut inline));
+
+        if !child.value().is_element() {
+            continue;
+        } else if let Ok(content) = parse_paragraph(child, diagnostics) {
+            contents.push(content);
+        } else {
+            diagnostics.skip(Stage::DocBlock, child);
+        }
+    }
+    contents.extend(take_inline(&mut inline));
+
+    contents
+}
+
+fn parse_footnotes<'a>(
+    maybe_footnotes: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Footnote<'a>>, ParseError> {
+    let footnotes = expect_element(maybe_footnotes, Stage::Footnotes, "div")?;
+
+    if !footnotes.has_class("footnotes", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Footnotes,
+            Expected::Class("footnotes"),
+            maybe_footnotes,
+        ));
+    }
+
+    let mut definitions = vec![];
+    for child in maybe_footnotes.children() {
+        if let Some(list) = child.value().as_element() {
+            if list.name() == "ol" {
+                let start = list
+                    .attr("start")
+                    .and_then(|start| start.parse().ok())
+                    .unwrap_or(1);
+                parse_footnote_list(child, start, &mut definitions, diagnostics);
+            }
+        }
+    }
+    Ok(definitions)
+}
+
+fn parse_footnote_list<'a>(
+    list: NodeRef<'a, Node>,
+    mut label: u32,
+    definitions: &mut Vec<Footnote<'a>>,
+    diagnostics: &mut Diagnostics,
+) {
+    for item in list.children() {
+        match item.value().as_element() {
+            Some(li) if li.name() == "li" => {}
+            _ => continue,
+        }
+        if let Some(id) = parse_anchor(item) {
+            let references = item
+                .descendants()
+                .filter_map(|node| node.value().as_element()?.attr("href")?.strip_prefix('#'))
+                .filter(|href| href.starts_with("fnref"))
+                .map(Cow::Borrowed)
+                .collect();
+            let mut contents = parse_blocks(item, diagnostics);
+            if let Some(Paragraph::Text(parts)) = contents.last_mut() {
+                trim_end(parts);
+            }
+
+            definitions.push(Footnote {
+                id,
+                label: Cow::Owned(label.to_string()),
+                contents,
+                references,
+            });
+        } else {
+            diagnostics.skip(Stage::Footnotes, item);
+        }
+        label += 1;
+    }
+}
+
+fn trim_end(parts: &mut Vec<TextPart>) {
+    while let Some(TextPart::Text(text)) = parts.last_mut() {
+        let trimmed = match text {
+            Cow::Borrowed(text) => Cow::Borrowed(text.trim_end()),
+            Cow::Owned(text) => Cow::Owned(text.trim_end().to_string()),
+        };
+        if !trimmed.is_empty() {
+            *text = trimmed;
+            break;
         }
+        parts.pop();
     }
+}
+
+fn last_section<'s, 'a>(sections: &'s mut Vec<Section<'a>>) -> &'s mut Section<'a> {
+    if sections.is_empty() {
+        sections.push(Section {
+            depth: 0,
+            heading: None,
+            contents: vec![],
+            anchor: None,
+            footnotes: vec![],
+        });
+    }
+    sections.last_mut().unwrap()
+}
 
-    Some(DocBlock { sections })
+fn push_content<'a>(sections: &mut Vec<Section<'a>>, content: Paragraph<'a>) {
+    last_section(sections).contents.push(content);
+}
+
+fn flush_inline<'a>(sections: &mut Vec<Section<'a>>, inline: &mut Vec<TextPart<'a>>) {
+    if let Some(content) = take_inline(inline) {
+        push_content(sections, content);
+    }
+}
+
+fn take_inline<'a>(inline: &mut Vec<TextPart<'a>>) -> Option<Paragraph<'a>> {
+    let is_blank = inline.iter().all(|part| match part {
+        TextPart::Text(text) => text.trim().is_empty(),
+        _ => false,
+    });
+    if is_blank {
+        inline.clear();
+        None
+    } else {
+        Some(Paragraph::Text(std::mem::take(inline)))
+    }
 }
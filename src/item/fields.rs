@@ -2,17 +2,104 @@ use ego_tree::NodeRef;
 use scraper::Node;
 use selectors::attr::CaseSensitivity;
 
-use crate::atom::{parse_text_inside, TextPart};
+use crate::{
+    anchor::parse_anchor,
+    atom::{parse_text_inside, TextPart},
+    diagnostics::Diagnostics,
+    error::{expect_element, Expected, ParseError, Stage},
+    header::{parse_doc_block, parse_item_info},
+};
 
-pub fn parse_struct_field_or_variant(maybe: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let element = maybe.value().as_element()?;
+use super::Item;
 
-    if !(element.name() == "span"
-        && (element.has_class("structfield", CaseSensitivity::CaseSensitive)
-            || element.has_class("variant", CaseSensitivity::CaseSensitive)))
+pub fn parse_struct_field_or_variant(maybe: NodeRef<Node>) -> Result<Vec<TextPart>, ParseError> {
+    let element = maybe
+        .value()
+        .as_element()
+        .ok_or_else(|| ParseError::new(Stage::Field, Expected::Element("span"), maybe))?;
+
+    if element.name() == "section" {
+        if !element.has_class("variant", CaseSensitivity::CaseSensitive) {
+            return Err(ParseError::new(
+                Stage::Field,
+                Expected::Class("variant"),
+                maybe,
+            ));
+        }
+
+        for child in maybe.children() {
+            if let Some(code_header) = child.value().as_element() {
+                if code_header.has_class("code-header", CaseSensitivity::CaseSensitive) {
+                    return Ok(parse_text_inside(child));
+                }
+            }
+        }
+        return Err(ParseError::new(
+            Stage::Field,
+            Expected::Child(".code-header"),
+            maybe,
+        ));
+    }
+
+    if !(element.name() == "span" || element.name() == "div") {
+        return Err(ParseError::new(
+            Stage::Field,
+            Expected::Element("span"),
+            maybe,
+        ));
+    }
+
+    if !(element.has_class("structfield", CaseSensitivity::CaseSensitive)
+        || element.has_class("variant", CaseSensitivity::CaseSensitive))
     {
-        return None;
+        return Err(ParseError::new(
+            Stage::Field,
+            Expected::Class("structfield"),
+            maybe,
+        ));
+    }
+
+    Ok(parse_text_inside(maybe))
+}
+
+pub fn parse_variants<'a>(
+    maybe_variants: NodeRef<'a, Node>,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Item<'a>>, ParseError> {
+    let variants = expect_element(maybe_variants, Stage::Field, "div")?;
+
+    if !variants.has_class("variants", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Field,
+            Expected::Class("variants"),
+            maybe_variants,
+        ));
+    }
+
+    let mut items: Vec<Item> = vec![];
+
+    for child in maybe_variants.children() {
+        if let Ok(variant) = parse_struct_field_or_variant(child) {
+            items.push(Item {
+                name: variant,
+                info: Default::default(),
+                description: None,
+                source: None,
+                anchor: parse_anchor(child),
+                notable_traits: vec![],
+            });
+        } else if let Ok(item_info) = parse_item_info(child) {
+            if let Some(last_item) = items.last_mut() {
+                last_item.info = item_info;
+            }
+        } else if let Ok(description) = parse_doc_block(child, diagnostics) {
+            if let Some(last_item) = items.last_mut() {
+                last_item.description = Some(description.sections);
+            }
+        } else {
+            diagnostics.skip(Stage::Field, child);
+        }
     }
 
-    Some(parse_text_inside(maybe))
+    Ok(items)
 }
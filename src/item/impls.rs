@@ -1,25 +1,95 @@
+use std::{borrow::Cow, collections::HashMap};
+
 use ego_tree::NodeRef;
-use scraper::Node;
+use scraper::{Html, Node, Selector};
 use selectors::attr::CaseSensitivity;
 
 use crate::{
-    atom::{parse_text_inside, parse_text_outside, TextPart},
+    anchor::parse_anchor,
+    atom::{parse_text_inside, parse_text_outside, plain_text, TextPart},
+    diagnostics::Diagnostics,
+    error::{expect_element, Expected, ParseError, Stage},
     header::{parse_doc_block, parse_item_info},
+    json::JsonParser,
+    source::{find_source_link, SourceLocation},
 };
 
 use super::{Impl, Item};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ImplCategory {
+    Inherent,
+    Trait,
+    Auto,
+    Blanket,
+    Deref,
+    Implementors,
+    AutoImplementors,
+    Foreign,
+}
+
+const IMPL_CATEGORIES: &[(&str, ImplCategory)] = &[
+    ("implementations", ImplCategory::Inherent),
+    ("methods", ImplCategory::Inherent),
+    ("trait-implementations", ImplCategory::Trait),
+    ("synthetic-implementations", ImplCategory::Auto),
+    ("blanket-implementations", ImplCategory::Blanket),
+    ("deref-methods", ImplCategory::Deref),
+    ("implementors", ImplCategory::Implementors),
+    ("synthetic-implementors", ImplCategory::AutoImplementors),
+    ("foreign-impls", ImplCategory::Foreign),
+];
+
+pub fn parse_impl_category(
+    maybe_section_header: NodeRef<Node>,
+    heading: &[TextPart],
+) -> Option<ImplCategory> {
+    let id = parse_anchor(maybe_section_header)?;
+
+    // Older rustdoc used `methods` for inherent impls and `implementations` for trait impls.
+    if id == "implementations" && plain_text(heading).trim_start().starts_with("Trait") {
+        return Some(ImplCategory::Trait);
+    }
+
+    IMPL_CATEGORIES
+        .iter()
+        .find(|(prefix, _)| {
+            id.strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('-'))
+        })
+        .map(|(_, category)| *category)
+}
+
 pub struct ImplHeading<'a> {
     pub title: Vec<TextPart<'a>>,
+    pub source: Option<SourceLocation<'a>>,
+    pub anchor: Option<Cow<'a, str>>,
 }
 
-pub fn parse_impl_heading(maybe_impl_header: NodeRef<Node>) -> Option<ImplHeading> {
-    let impl_header = maybe_impl_header.value().as_element()?;
-
-    if !(impl_header.name() == "h3"
-        && impl_header.has_class("impl", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_impl_heading(maybe_impl_header: NodeRef<Node>) -> Result<ImplHeading, ParseError> {
+    let impl_header = maybe_impl_header
+        .value()
+        .as_element()
+        .filter(|impl_header| impl_header.name() == "h3" || impl_header.name() == "section")
+        .ok_or_else(|| {
+            ParseError::new(
+                Stage::ImplHeading,
+                Expected::Element("h3"),
+                maybe_impl_header,
+            )
+        })?;
+
+    if !impl_header.has_class("impl", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::ImplHeading,
+            Expected::Class("impl"),
+            maybe_impl_header,
+        ));
     }
 
     for child in maybe_impl_header.children() {
@@ -27,201 +97,484 @@ pub fn parse_impl_heading(maybe_impl_header: NodeRef<Node>) -> Option<ImplHeadin
             if element.name() == "code"
                 && element.has_class("in-band", CaseSensitivity::CaseSensitive)
             {
-                return Some(ImplHeading {
+                return Ok(ImplHeading {
                     title: parse_text_outside(child),
+                    source: find_source_link(maybe_impl_header),
+                    anchor: parse_anchor(maybe_impl_header),
+                });
+            } else if element.name() == "h3"
+                && element.has_class("code-header", CaseSensitivity::CaseSensitive)
+            {
+                return Ok(ImplHeading {
+                    title: parse_text_inside(child),
+                    source: find_source_link(maybe_impl_header),
+                    anchor: parse_anchor(maybe_impl_header),
                 });
             }
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::ImplHeading,
+        Expected::Child("code.in-band"),
+        maybe_impl_header,
+    ))
 }
 
-pub fn parse_empty_impl(maybe_empty_impl: NodeRef<Node>) -> Option<Impl> {
-    let empty_impl = maybe_empty_impl.value().as_element()?;
-
-    if !(empty_impl.name() == "div" && empty_impl.has_class("impl", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_empty_impl(maybe_empty_impl: NodeRef<Node>) -> Result<Impl, ParseError> {
+    let empty_impl = maybe_empty_impl
+        .value()
+        .as_element()
+        .filter(|empty_impl| empty_impl.name() == "div" || empty_impl.name() == "section")
+        .ok_or_else(|| {
+            ParseError::new(
+                Stage::EmptyImpl,
+                Expected::AnyElement(&["div", "section"]),
+                maybe_empty_impl,
+            )
+        })?;
+
+    if !empty_impl.has_class("impl", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::EmptyImpl,
+            Expected::Class("impl"),
+            maybe_empty_impl,
+        ));
     }
 
     for child in maybe_empty_impl.children() {
         if let Some(element) = child.value().as_element() {
             if element.name() == "h3"
-                && element.has_class("in-band", CaseSensitivity::CaseSensitive)
+                && (element.has_class("in-band", CaseSensitivity::CaseSensitive)
+                    || element.has_class("code-header", CaseSensitivity::CaseSensitive))
             {
-                return Some(Impl {
-                    target: parse_text_inside(child),
-                    items: vec![],
-                });
+                return Ok(Impl::new(
+                    parse_text_inside(child),
+                    vec![],
+                    find_source_link(maybe_empty_impl),
+                    parse_anchor(maybe_empty_impl),
+                ));
             }
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::EmptyImpl,
+        Expected::Child("h3.in-band"),
+        maybe_empty_impl,
+    ))
 }
 
-pub fn parse_impl_items(maybe_impl_items: NodeRef<Node>) -> Option<Vec<Item>> {
-    let impl_items = maybe_impl_items.value().as_element()?;
+pub fn parse_impl_items<'a>(
+    maybe_impl_items: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Item<'a>>, ParseError> {
+    let impl_items = expect_element(maybe_impl_items, Stage::ImplItems, "div")?;
+
+    if !impl_items.has_class("impl-items", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::ImplItems,
+            Expected::Class("impl-items"),
+            maybe_impl_items,
+        ));
+    }
 
-    if !(impl_items.name() == "div"
-        && impl_items.has_class("impl-items", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+    Ok(parse_items(maybe_impl_items, notable_traits, diagnostics))
+}
+
+pub fn parse_trait_items<'a>(
+    maybe_trait_items: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Item<'a>>, ParseError> {
+    let trait_items = expect_element(maybe_trait_items, Stage::TraitItems, "div")?;
+
+    if !trait_items.has_class("methods", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::TraitItems,
+            Expected::Class("methods"),
+            maybe_trait_items,
+        ));
     }
 
+    Ok(parse_items(maybe_trait_items, notable_traits, diagnostics))
+}
+
+fn parse_items<'a>(
+    parent: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Vec<Item<'a>> {
     let mut items = vec![];
 
-    for child in maybe_impl_items.children() {
-        if let Some(heading) = parse_item_heading(child) {
+    for child in parent.children() {
+        if let Ok(heading) = parse_item_heading(child, notable_traits) {
             items.push(Item {
                 name: heading.title,
                 info: Default::default(),
                 description: None,
+                source: heading.source,
+                anchor: heading.anchor,
+                notable_traits: heading.notable_traits,
             })
-        } else if let Some(item_info) = parse_item_info(child) {
+        } else if let Ok(item_info) = parse_item_info(child) {
             if let Some(last_item) = items.last_mut() {
                 last_item.info = item_info;
             }
-        } else if let Some(doc_block) = parse_doc_block(child) {
+        } else if let Ok(doc_block) = parse_doc_block(child, diagnostics) {
             if let Some(last_item) = items.last_mut() {
                 last_item.description = Some(doc_block.sections);
             }
-        } else if let Some(name) = parse_srclink(child) {
+        } else if let Ok(srclink) = parse_srclink(child, notable_traits) {
             items.push(Item {
-                name,
+                name: srclink.header,
                 info: Default::default(),
                 description: None,
+                source: srclink.source,
+                anchor: srclink.anchor,
+                notable_traits: srclink.notable_traits,
             });
-        } else if let Some(toggle) = parse_toggle_item(child) {
+        } else if let Ok(toggle) = parse_toggle_item(child, notable_traits, diagnostics) {
             items.push(toggle);
+        } else {
+            diagnostics.skip(Stage::ImplItems, child);
         }
     }
-    Some(items)
+    items
 }
 
 struct ItemHeading<'a> {
     title: Vec<TextPart<'a>>,
+    source: Option<SourceLocation<'a>>,
+    anchor: Option<Cow<'a, str>>,
+    notable_traits: Vec<Impl<'a>>,
 }
 
-fn parse_item_heading(maybe_item_heading: NodeRef<Node>) -> Option<ItemHeading> {
-    let item_heading = maybe_item_heading.value().as_element()?;
-
-    if item_heading.name() != "h4" {
-        return None;
-    }
+fn parse_item_heading<'a>(
+    maybe_item_heading: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+) -> Result<ItemHeading<'a>, ParseError> {
+    expect_element(maybe_item_heading, Stage::ItemHeading, "h4")?;
 
     for child in maybe_item_heading.children() {
         if let Some(element) = child.value().as_element() {
             if element.name() == "code" {
-                return Some(ItemHeading {
+                return Ok(ItemHeading {
                     title: parse_text_outside(child),
+                    source: find_source_link(maybe_item_heading),
+                    anchor: parse_anchor(maybe_item_heading),
+                    notable_traits: parse_notable_traits(child, notable_traits),
                 });
             }
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::ItemHeading,
+        Expected::Child("code"),
+        maybe_item_heading,
+    ))
 }
 
-pub fn parse_impl_div(maybe_impl_list: NodeRef<Node>) -> Option<Vec<Impl>> {
-    let impl_list = maybe_impl_list.value().as_element()?;
-
-    if impl_list.name() != "div" {
-        return None;
-    }
+pub fn parse_impl_div<'a>(
+    maybe_impl_list: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Vec<Impl<'a>>, ParseError> {
+    expect_element(maybe_impl_list, Stage::ImplDiv, "div")?;
 
     let mut impls = vec![];
+    let mut local_diagnostics = Diagnostics::new();
 
     for child in maybe_impl_list.children() {
-        if let Some(empty) = parse_empty_impl(child) {
+        if !child.value().is_element() || is_negative_marker(child) {
+            continue;
+        } else if let Ok(empty) = parse_empty_impl(child) {
             impls.push(empty);
-        } else if let Some(heading) = parse_impl_heading(child) {
-            impls.push(Impl {
-                target: heading.title,
-                items: vec![],
-            });
-        } else if let Some(items) = parse_impl_items(child) {
+        } else if let Ok(implementor) =
+            parse_implementor(child, notable_traits, &mut local_diagnostics)
+        {
+            impls.push(implementor);
+        } else if let Ok(heading) = parse_impl_heading(child) {
+            impls.push(Impl::new(
+                heading.title,
+                vec![],
+                heading.source,
+                heading.anchor,
+            ));
+        } else if let Ok(items) = parse_impl_items(child, notable_traits, &mut local_diagnostics) {
             if let Some(last_impl) = impls.last_mut() {
                 last_impl.items = items;
             }
+        } else if is_doc_block(child) {
+            local_diagnostics.skip(Stage::ImplDiv, child);
         } else {
-            return None;
+            return Err(ParseError::new(
+                Stage::ImplDiv,
+                Expected::Class("impl"),
+                child,
+            ));
         }
     }
 
-    Some(impls)
+    diagnostics.append(&mut local_diagnostics);
+    Ok(impls)
 }
 
-pub fn parse_implementor(maybe_implementor: NodeRef<Node>) -> Option<Impl> {
-    let implementor = maybe_implementor.value().as_element()?;
-
-    if !(implementor.name() == "details"
-        && implementor.has_class("implementors-toggle", CaseSensitivity::CaseSensitive))
-    {
-        return None;
-    }
+fn is_doc_block(node: NodeRef<Node>) -> bool {
+    node.value().as_element().is_some_and(|element| {
+        element.name() == "div" && element.has_class("docblock", CaseSensitivity::CaseSensitive)
+    })
+}
 
-    let mut children = maybe_implementor.children();
-    let maybe_summary = children.next()?;
-    let summary = maybe_summary.value().as_element()?;
+fn is_negative_marker(node: NodeRef<Node>) -> bool {
+    node.value()
+        .as_element()
+        .is_some_and(|element| element.has_class("negative-marker", CaseSensitivity::CaseSensitive))
+}
 
-    if summary.name() != "summary" {
-        return None;
+pub fn parse_implementor<'a>(
+    maybe_implementor: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Impl<'a>, ParseError> {
+    let implementor = expect_element(maybe_implementor, Stage::Implementor, "details")?;
+
+    if !implementor.has_class("implementors-toggle", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Implementor,
+            Expected::Class("implementors-toggle"),
+            maybe_implementor,
+        ));
     }
 
-    let target = parse_srclink(maybe_summary.first_child()?)?;
+    let mut children = maybe_implementor.children();
+    let maybe_summary = children.next().ok_or_else(|| {
+        ParseError::new(
+            Stage::Implementor,
+            Expected::Child("summary"),
+            maybe_implementor,
+        )
+    })?;
+    expect_element(maybe_summary, Stage::Implementor, "summary")?;
+
+    let maybe_srclink = maybe_summary.first_child().ok_or_else(|| {
+        ParseError::new(
+            Stage::Implementor,
+            Expected::Child("div.has-srclink"),
+            maybe_summary,
+        )
+    })?;
+    let srclink = parse_srclink(maybe_srclink, notable_traits)?;
 
     let items = children
         .next()
-        .and_then(parse_impl_items)
+        .and_then(|items| parse_impl_items(items, notable_traits, diagnostics).ok())
         .unwrap_or_default();
 
-    Some(Impl { target, items })
+    Ok(Impl::new(
+        srclink.header,
+        items,
+        srclink.source,
+        srclink.anchor,
+    ))
 }
 
-pub fn parse_implementor_or_empty(node: NodeRef<Node>) -> Option<Impl> {
-    parse_implementor(node).or_else(|| parse_empty_impl(node))
+pub fn parse_implementor_or_empty<'a>(
+    node: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Impl<'a>, ParseError> {
+    parse_implementor(node, notable_traits, diagnostics).or_else(|_| parse_empty_impl(node))
 }
 
-fn parse_srclink(maybe_srclink: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let srclink = maybe_srclink.value().as_element()?;
+struct Srclink<'a> {
+    header: Vec<TextPart<'a>>,
+    source: Option<SourceLocation<'a>>,
+    anchor: Option<Cow<'a, str>>,
+    notable_traits: Vec<Impl<'a>>,
+}
 
-    if !(srclink.name() == "div"
-        && srclink.has_class("has-srclink", CaseSensitivity::CaseSensitive))
+fn parse_srclink<'a>(
+    maybe_srclink: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+) -> Result<Srclink<'a>, ParseError> {
+    let srclink = maybe_srclink
+        .value()
+        .as_element()
+        .filter(|srclink| srclink.name() == "div" || srclink.name() == "section")
+        .ok_or_else(|| {
+            ParseError::new(
+                Stage::Srclink,
+                Expected::AnyElement(&["div", "section"]),
+                maybe_srclink,
+            )
+        })?;
+
+    if srclink.name() == "div" && !srclink.has_class("has-srclink", CaseSensitivity::CaseSensitive)
     {
-        return None;
+        return Err(ParseError::new(
+            Stage::Srclink,
+            Expected::Class("has-srclink"),
+            maybe_srclink,
+        ));
     }
 
     for child in maybe_srclink.children() {
         if let Some(code_header) = child.value().as_element() {
             if code_header.has_class("code-header", CaseSensitivity::CaseSensitive) {
-                return Some(parse_text_inside(child));
+                return Ok(Srclink {
+                    header: parse_text_inside(child),
+                    source: find_source_link(maybe_srclink),
+                    anchor: parse_anchor(maybe_srclink),
+                    notable_traits: parse_notable_traits(child, notable_traits),
+                });
             }
         }
     }
-    None
+    Err(ParseError::new(
+        Stage::Srclink,
+        Expected::Child(".code-header"),
+        maybe_srclink,
+    ))
 }
 
-fn parse_toggle_item(maybe_toggle_item: NodeRef<Node>) -> Option<Item> {
-    let toggle_item = maybe_toggle_item.value().as_element()?;
+fn parse_notable_traits<'a>(
+    maybe_code_header: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+) -> Vec<Impl<'a>> {
+    let has_class = |node: &NodeRef<Node>, class| {
+        node.value()
+            .as_element()
+            .is_some_and(|element| element.has_class(class, CaseSensitivity::CaseSensitive))
+    };
 
-    if toggle_item.name() != "details" {
-        return None;
+    let mut impls = vec![];
+    for node in maybe_code_header.descendants() {
+        if has_class(&node, "notable-traits-tooltiptext") {
+            // Before 1.66, rustdoc inlined the tooltip next to the return type
+            for content in node.descendants().filter(|node| has_class(node, "content")) {
+                impls.extend(parse_notable_impls(content));
+            }
+        } else if let Some(ty) = node
+            .value()
+            .as_element()
+            .and_then(|element| element.attr("data-notable-ty"))
+        {
+            impls.extend(notable_traits.get(ty).into_iter().flatten().cloned());
+        }
     }
+    impls
+}
 
-    let mut children = maybe_toggle_item.children();
-    let maybe_summary = children.next()?;
-    let summary = maybe_summary.value().as_element()?;
+// Newer rustdoc keeps every tooltip of the page in one JSON object keyed by the return type
+pub type NotableTraits = HashMap<String, Vec<Impl<'static>>>;
 
-    if summary.name() != "summary" {
-        return None;
-    }
+pub fn parse_notable_traits_data(html: &Html) -> NotableTraits {
+    let select_data = Selector::parse("script#notable-traits-data").unwrap();
+    let entries = html
+        .select(&select_data)
+        .next()
+        .and_then(|script| script.text().next())
+        .and_then(|data| JsonParser::new(data).parse_string_map())
+        .unwrap_or_default();
 
-    let srclink = parse_srclink(maybe_summary.first_child()?)?;
+    entries
+        .into_iter()
+        .map(|(ty, tooltip)| (ty.into_owned(), parse_notable_tooltip(&tooltip)))
+        .collect()
+}
+
+fn parse_notable_tooltip(tooltip: &str) -> Vec<Impl<'static>> {
+    let fragment = Html::parse_fragment(tooltip);
+    let code = fragment.tree.root().descendants().find(|node| {
+        node.value()
+            .as_element()
+            .is_some_and(|element| element.name() == "code")
+            && node.parent().is_some_and(|parent| {
+                parent
+                    .value()
+                    .as_element()
+                    .is_some_and(|element| element.name() == "pre")
+            })
+    });
+    code.map(parse_notable_impls)
+        .unwrap_or_default()
+        .into_iter()
+        .map(Impl::into_owned)
+        .collect()
+}
+
+fn parse_notable_impls(content: NodeRef<Node>) -> Vec<Impl> {
+    let mut impls: Vec<Impl> = vec![];
+    for line in content.children().filter(|line| line.value().is_element()) {
+        let text = parse_text_inside(line);
+        let plain = plain_text(&text);
+        if plain.trim_start().starts_with("impl") {
+            impls.push(Impl::new(text, vec![], None, None));
+        } else if let Some(last_impl) = impls.last_mut().filter(|_| !plain.trim().is_empty()) {
+            last_impl.items.push(Item {
+                name: text,
+                info: Default::default(),
+                description: None,
+                source: None,
+                anchor: None,
+                notable_traits: vec![],
+            });
+        }
+    }
+    impls
+}
 
-    let doc_block = parse_doc_block(children.next()?)?;
+fn parse_toggle_item<'a>(
+    maybe_toggle_item: NodeRef<'a, Node>,
+    notable_traits: &NotableTraits,
+    diagnostics: &mut Diagnostics,
+) -> Result<Item<'a>, ParseError> {
+    expect_element(maybe_toggle_item, Stage::ToggleItem, "details")?;
 
-    Some(Item {
-        name: srclink,
-        info: Default::default(),
+    let mut children = maybe_toggle_item.children();
+    let maybe_summary = children.next().ok_or_else(|| {
+        ParseError::new(
+            Stage::ToggleItem,
+            Expected::Child("summary"),
+            maybe_toggle_item,
+        )
+    })?;
+    expect_element(maybe_summary, Stage::ToggleItem, "summary")?;
+
+    let maybe_srclink = maybe_summary.first_child().ok_or_else(|| {
+        ParseError::new(
+            Stage::ToggleItem,
+            Expected::Child("div.has-srclink"),
+            maybe_summary,
+        )
+    })?;
+    let srclink = parse_srclink(maybe_srclink, notable_traits)?;
+
+    let mut info = maybe_srclink
+        .next_siblings()
+        .find_map(|sibling| parse_item_info(sibling).ok());
+    let mut doc_block = None;
+    for child in children.filter(|child| child.value().is_element()) {
+        if let Ok(item_info) = parse_item_info(child) {
+            info = info.or(Some(item_info));
+        } else if doc_block.is_none() {
+            doc_block = parse_doc_block(child, diagnostics).ok();
+        }
+    }
+    let doc_block = doc_block.ok_or_else(|| {
+        ParseError::new(
+            Stage::ToggleItem,
+            Expected::Child("div.docblock"),
+            maybe_toggle_item,
+        )
+    })?;
+
+    Ok(Item {
+        name: srclink.header,
+        info: info.unwrap_or_default(),
         description: Some(doc_block.sections),
+        source: srclink.source,
+        anchor: srclink.anchor,
+        notable_traits: srclink.notable_traits,
     })
 }
@@ -1,57 +1,142 @@
+use std::borrow::Cow;
+
 use ego_tree::NodeRef;
 use scraper::Node;
-use selectors::attr::CaseSensitivity;
 
 use crate::{
-    atom::{parse_text_inside, TextPart},
+    atom::{into_owned, into_owned_parts, parse_text_inside, TextPart},
+    error::{expect_element, Expected, ParseError, Stage},
     header::{ItemInfo, Section},
+    profile::Profile,
+    source::SourceLocation,
+    syntax::{
+        impl_header::{parse_impl_header, ImplHeader},
+        signature::{parse_signature, Signature},
+    },
 };
 
 pub mod fields;
 pub mod impls;
 pub mod table;
 
-pub fn parse_item_header(maybe_section_header: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let section_header = maybe_section_header.value().as_element()?;
+pub fn parse_item_header<'a>(
+    maybe_section_header: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<Vec<TextPart<'a>>, ParseError> {
+    let section_header = expect_element(maybe_section_header, Stage::ItemHeader, "h2")?;
 
-    if !(section_header.name() == "h2"
-        && (section_header.has_class("section-header", CaseSensitivity::CaseSensitive)
-            || section_header.has_class("small-section-header", CaseSensitivity::CaseSensitive)))
-    {
-        return None;
+    if !profile.is_section_header(section_header) {
+        return Err(ParseError::new(
+            Stage::ItemHeader,
+            Expected::Class(profile.section_headers[0]),
+            maybe_section_header,
+        ));
     }
 
-    Some(parse_text_inside(maybe_section_header))
+    Ok(parse_text_inside(maybe_section_header))
 }
 
-pub fn is_item_header(maybe_section_header: NodeRef<Node>) -> bool {
+pub fn is_item_header(maybe_section_header: NodeRef<Node>, profile: &Profile) -> bool {
     maybe_section_header
         .value()
         .as_element()
-        .map_or(false, |section_header| {
-            section_header.name() == "h2"
-                && (section_header.has_class("section-header", CaseSensitivity::CaseSensitive)
-                    || section_header
-                        .has_class("small-section-header", CaseSensitivity::CaseSensitive))
+        .is_some_and(|section_header| {
+            section_header.name() == "h2" && profile.is_section_header(section_header)
         })
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemRow<'a> {
     pub name: Vec<TextPart<'a>>,
     pub info: ItemInfo<'a>,
     pub summary: Vec<TextPart<'a>>,
+    /// Rustdoc gives rows no `id`, so this is taken from the row's link, e.g. `struct.Sleep`.
+    pub anchor: Option<Cow<'a, str>>,
 }
 
-#[derive(Debug)]
+impl ItemRow<'_> {
+    pub fn into_owned(self) -> ItemRow<'static> {
+        ItemRow {
+            name: into_owned_parts(self.name),
+            info: self.info.into_owned(),
+            summary: into_owned_parts(self.summary),
+            anchor: self.anchor.map(into_owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Item<'a> {
     pub name: Vec<TextPart<'a>>,
     pub info: ItemInfo<'a>,
     pub description: Option<Vec<Section<'a>>>,
+    pub source: Option<SourceLocation<'a>>,
+    pub anchor: Option<Cow<'a, str>>,
+    /// Rustdoc's tooltip for a return type with a notable trait such as `Iterator`, read from the
+    /// inline tooltips of older pages or the `notable-traits-data` JSON of newer ones. Each
+    /// impl's `items` hold its associated types, e.g. `type Item = &'a T;`.
+    pub notable_traits: Vec<Impl<'a>>,
 }
 
-#[derive(Debug)]
+impl<'a> Item<'a> {
+    pub fn signature(&self) -> Option<Signature<'a>> {
+        parse_signature(&self.name)
+    }
+
+    pub fn into_owned(self) -> Item<'static> {
+        Item {
+            name: into_owned_parts(self.name),
+            info: self.info.into_owned(),
+            description: self
+                .description
+                .map(|sections| sections.into_iter().map(Section::into_owned).collect()),
+            source: self.source.map(SourceLocation::into_owned),
+            anchor: self.anchor.map(into_owned),
+            notable_traits: self
+                .notable_traits
+                .into_iter()
+                .map(Impl::into_owned)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Impl<'a> {
     pub target: Vec<TextPart<'a>>,
+    /// `None` if `target` could not be parsed.
+    pub header: Option<ImplHeader<'a>>,
     pub items: Vec<Item<'a>>,
+    pub source: Option<SourceLocation<'a>>,
+    pub anchor: Option<Cow<'a, str>>,
+}
+
+impl<'a> Impl<'a> {
+    pub(crate) fn new(
+        target: Vec<TextPart<'a>>,
+        items: Vec<Item<'a>>,
+        source: Option<SourceLocation<'a>>,
+        anchor: Option<Cow<'a, str>>,
+    ) -> Self {
+        Impl {
+            header: parse_impl_header(&target),
+            target,
+            items,
+            source,
+            anchor,
+        }
+    }
+
+    pub fn into_owned(self) -> Impl<'static> {
+        Impl {
+            target: into_owned_parts(self.target),
+            header: self.header.map(ImplHeader::into_owned),
+            items: self.items.into_iter().map(Item::into_owned).collect(),
+            source: self.source.map(SourceLocation::into_owned),
+            anchor: self.anchor.map(into_owned),
+        }
+    }
 }
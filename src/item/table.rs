@@ -1,167 +1,217 @@
+use std::borrow::Cow;
+
 use ego_tree::NodeRef;
 use scraper::Node;
-use selectors::attr::CaseSensitivity;
 
 use crate::{
+    anchor::parse_row_anchor,
     atom::{
         parse_deprecated, parse_portability, parse_text_inside, parse_text_outside, parse_unstable,
         TextPart,
     },
+    error::{expect_element, Expected, ParseError, Stage},
     header::ItemInfo,
     item::ItemRow,
+    profile::{ItemTableLayout, Profile, RowTable},
 };
 
-pub fn parse_item_table(maybe_item_table: NodeRef<Node>) -> Option<Vec<ItemRow>> {
-    let item_table = maybe_item_table.value().as_element()?;
-
-    if !(item_table.name() == "div"
-        && item_table.has_class("item-table", CaseSensitivity::CaseSensitive))
-    {
-        return None;
+pub fn parse_table<'a>(
+    maybe_table: NodeRef<'a, Node>,
+    profile: &Profile,
+) -> Result<Vec<ItemRow<'a>>, ParseError> {
+    let mut first_error = None;
+    for profile in profile.fallbacks() {
+        let table = match &profile.item_table {
+            ItemTableLayout::Block => parse_block_table(maybe_table),
+            ItemTableLayout::Rows(layout) => parse_item_table(maybe_table, layout),
+        };
+        match table {
+            Ok(table) => return Ok(table),
+            Err(error) => {
+                first_error.get_or_insert(error);
+            }
+        }
     }
+    Err(first_error.unwrap())
+}
+
+pub fn parse_item_table<'a>(
+    maybe_item_table: NodeRef<'a, Node>,
+    layout: &RowTable,
+) -> Result<Vec<ItemRow<'a>>, ParseError> {
+    layout.table.expect(maybe_item_table, Stage::ItemTable)?;
 
     let mut rows = vec![];
 
-    let mut children = maybe_item_table.children();
+    let mut children = maybe_item_table
+        .children()
+        .filter(|child| child.value().is_element());
 
     while let Some(child) = children.next() {
-        if let Some(row) = parse_item_row(child) {
+        if let Ok(row) = parse_item_row(child, layout) {
             rows.push(row);
         } else {
-            let left = parse_item_left(child)?;
-            let right = parse_item_right(children.next()?)?;
+            let left = parse_item_left(child, layout)?;
+            let maybe_right = children.next().ok_or_else(|| {
+                ParseError::new(
+                    Stage::ItemTable,
+                    Expected::Child(layout.right.selector()),
+                    child,
+                )
+            })?;
+            let right = parse_item_right(maybe_right, layout)?;
             rows.push(ItemRow {
                 name: left.text,
                 info: left.info,
                 summary: right,
+                anchor: left.anchor,
             });
         }
     }
 
-    Some(rows)
+    Ok(rows)
 }
 
-fn parse_item_row(maybe_item_row: NodeRef<Node>) -> Option<ItemRow> {
-    let item_row = maybe_item_row.value().as_element()?;
-
-    if !(item_row.name() == "div" && item_row.has_class("item-row", CaseSensitivity::CaseSensitive))
-    {
-        return None;
-    }
-
-    let mut children = maybe_item_row.children();
-    let left = parse_item_left(children.next()?)?;
-    let right = parse_item_right(children.next()?)?;
-
-    Some(ItemRow {
+fn parse_item_row<'a>(
+    maybe_item_row: NodeRef<'a, Node>,
+    layout: &RowTable,
+) -> Result<ItemRow<'a>, ParseError> {
+    let row = layout.row.ok_or_else(|| {
+        ParseError::new(
+            Stage::ItemRow,
+            Expected::Child(layout.left.selector()),
+            maybe_item_row,
+        )
+    })?;
+    row.expect(maybe_item_row, Stage::ItemRow)?;
+
+    let mut children = maybe_item_row
+        .children()
+        .filter(|child| child.value().is_element());
+    let maybe_left = children.next().ok_or_else(|| {
+        ParseError::new(
+            Stage::ItemRow,
+            Expected::Child(layout.left.selector()),
+            maybe_item_row,
+        )
+    })?;
+    let left = parse_item_left(maybe_left, layout)?;
+    let maybe_right = children.next().ok_or_else(|| {
+        ParseError::new(
+            Stage::ItemRow,
+            Expected::Child(layout.right.selector()),
+            maybe_item_row,
+        )
+    })?;
+    let right = parse_item_right(maybe_right, layout)?;
+
+    Ok(ItemRow {
         name: left.text,
         info: left.info,
         summary: right,
+        anchor: left.anchor,
     })
 }
 
 struct ItemLeft<'a> {
     text: Vec<TextPart<'a>>,
     info: ItemInfo<'a>,
+    anchor: Option<Cow<'a, str>>,
 }
 
-fn parse_item_left(maybe_item_left: NodeRef<Node>) -> Option<ItemLeft> {
-    let item_left = maybe_item_left.value().as_element()?;
-
-    if !(item_left.name() == "div"
-        && item_left.has_class("item-left", CaseSensitivity::CaseSensitive))
-    {
-        return None;
-    }
+fn parse_item_left<'a>(
+    maybe_item_left: NodeRef<'a, Node>,
+    layout: &RowTable,
+) -> Result<ItemLeft<'a>, ParseError> {
+    layout.left.expect(maybe_item_left, Stage::ItemLeft)?;
 
     let mut children = maybe_item_left.children();
-    let text = parse_text_outside(children.next()?);
+    let maybe_name = children
+        .next()
+        .ok_or_else(|| ParseError::new(Stage::ItemLeft, Expected::Child("a"), maybe_item_left))?;
+    let text = parse_text_outside(maybe_name);
 
     let mut stability = None;
     let mut portability = None;
     let mut deprecation = None;
     for child in children {
-        stability = stability.or_else(|| parse_unstable(child));
-        portability = portability.or_else(|| parse_portability(child));
-        deprecation = deprecation.or_else(|| parse_deprecated(child));
+        stability = stability.or_else(|| parse_unstable(child).ok());
+        portability = portability.or_else(|| parse_portability(child).ok());
+        deprecation = deprecation.or_else(|| parse_deprecated(child).ok());
     }
 
-    Some(ItemLeft {
+    Ok(ItemLeft {
         text,
         info: ItemInfo {
             stability,
             portability,
             deprecation,
         },
+        anchor: parse_row_anchor(maybe_name),
     })
 }
 
-fn parse_item_right(maybe_item_right: NodeRef<Node>) -> Option<Vec<TextPart>> {
-    let item_right = maybe_item_right.value().as_element()?;
-
-    if !(item_right.name() == "div"
-        && item_right.has_class("item-right", CaseSensitivity::CaseSensitive))
-    {
-        return None;
-    }
+fn parse_item_right<'a>(
+    maybe_item_right: NodeRef<'a, Node>,
+    layout: &RowTable,
+) -> Result<Vec<TextPart<'a>>, ParseError> {
+    layout.right.expect(maybe_item_right, Stage::ItemRight)?;
 
-    Some(parse_text_inside(maybe_item_right))
+    Ok(parse_text_inside(maybe_item_right))
 }
 
-pub fn parse_block_table(maybe_table: NodeRef<Node>) -> Option<Vec<ItemRow>> {
-    let table = maybe_table.value().as_element()?;
+pub fn parse_block_table(maybe_table: NodeRef<Node>) -> Result<Vec<ItemRow>, ParseError> {
+    let table = expect_element(maybe_table, Stage::BlockTable, "table")?;
 
-    if !(table.name() == "table" && table.attr("style") == Some("display: block;")) {
-        return None;
+    if table.attr("style") != Some("display: block;") {
+        return Err(ParseError::new(
+            Stage::BlockTable,
+            Expected::Attribute("style", "display: block;"),
+            maybe_table,
+        ));
     }
 
-    let maybe_tbody = maybe_table.first_child()?;
-    let tbody = maybe_tbody.value().as_element()?;
-
-    if tbody.name() != "tbody" {
-        return None;
-    }
+    let maybe_tbody = maybe_table
+        .first_child()
+        .ok_or_else(|| ParseError::new(Stage::BlockTable, Expected::Child("tbody"), maybe_table))?;
+    expect_element(maybe_tbody, Stage::BlockTable, "tbody")?;
 
     let mut rows = vec![];
     for child in maybe_tbody.children() {
-        let tr = child.value().as_element()?;
-
-        if tr.name() != "tr" {
-            return None;
-        }
+        expect_element(child, Stage::BlockTable, "tr")?;
 
         let mut children = child.children();
-        let maybe_left = children.next()?;
-        let left = maybe_left.value().as_element()?;
-
-        if left.name() != "td" {
-            return None;
-        }
+        let maybe_left = children
+            .next()
+            .ok_or_else(|| ParseError::new(Stage::BlockTable, Expected::Child("td"), child))?;
+        expect_element(maybe_left, Stage::BlockTable, "td")?;
 
         let mut left_children = maybe_left.children();
 
-        let text = parse_text_outside(left_children.next()?);
+        let maybe_name = left_children
+            .next()
+            .ok_or_else(|| ParseError::new(Stage::BlockTable, Expected::Child("a"), maybe_left))?;
+        let text = parse_text_outside(maybe_name);
 
         let mut stability = None;
         let mut portability = None;
         let mut deprecation = None;
         for child in left_children {
-            stability = stability.or_else(|| parse_unstable(child));
-            portability = portability.or_else(|| parse_portability(child));
-            deprecation = deprecation.or_else(|| parse_deprecated(child));
+            stability = stability.or_else(|| parse_unstable(child).ok());
+            portability = portability.or_else(|| parse_portability(child).ok());
+            deprecation = deprecation.or_else(|| parse_deprecated(child).ok());
         }
 
-        fn parse_right(maybe_right: NodeRef<Node>) -> Option<Vec<TextPart>> {
-            let right = maybe_right.value().as_element()?;
-
-            if right.name() != "td" {
-                return None;
-            }
+        fn parse_right(maybe_right: NodeRef<Node>) -> Result<Vec<TextPart>, ParseError> {
+            expect_element(maybe_right, Stage::BlockTable, "td")?;
 
-            Some(parse_text_inside(maybe_right))
+            Ok(parse_text_inside(maybe_right))
         }
 
-        let right = children.next().and_then(parse_right).unwrap_or_default();
+        let right = children
+            .next()
+            .and_then(|right| parse_right(right).ok())
+            .unwrap_or_default();
 
         rows.push(ItemRow {
             name: text,
@@ -171,8 +221,9 @@ pub fn parse_block_table(maybe_table: NodeRef<Node>) -> Option<Vec<ItemRow>> {
                 deprecation,
             },
             summary: right,
+            anchor: parse_row_anchor(maybe_name),
         })
     }
 
-    Some(rows)
+    Ok(rows)
 }
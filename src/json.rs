@@ -0,0 +1,141 @@
+use std::borrow::Cow;
+
+// Parses the small JSON subset rustdoc embeds in its pages and scripts
+pub struct JsonParser<'a> {
+    input: &'a str,
+}
+
+impl<'a> JsonParser<'a> {
+    pub fn new(input: &'a str) -> Self {
+        JsonParser { input }
+    }
+
+    pub fn parse_string_map(&mut self) -> Option<Vec<(Cow<'a, str>, Cow<'a, str>)>> {
+        let mut entries = vec![];
+
+        self.expect('{')?;
+        if self.eat('}') {
+            return Some(entries);
+        }
+        loop {
+            let key = self.parse_string()?;
+            self.expect(':')?;
+            entries.push((key, self.parse_string()?));
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect('}')?;
+
+        Some(entries)
+    }
+
+    pub fn parse_string(&mut self) -> Option<Cow<'a, str>> {
+        self.expect('"')?;
+
+        let end = self.input.find(['"', '\\'])?;
+        if self.input[end..].starts_with('"') {
+            let text = &self.input[..end];
+            self.input = &self.input[end + 1..];
+            return Some(Cow::Borrowed(text));
+        }
+
+        let mut text = String::new();
+        let mut chars = self.input.char_indices();
+        while let Some((index, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.input = &self.input[index + 1..];
+                    return Some(Cow::Owned(text));
+                }
+                '\\' => match chars.next()?.1 {
+                    'n' => text.push('\n'),
+                    't' => text.push('\t'),
+                    'r' => text.push('\r'),
+                    'b' => text.push('\u{8}'),
+                    'f' => text.push('\u{c}'),
+                    'u' => {
+                        let mut code = parse_hex(&mut chars)?;
+                        // Characters outside the BMP are escaped as a UTF-16 surrogate pair
+                        if (0xd800..0xdc00).contains(&code) {
+                            if chars.next()?.1 != '\\' || chars.next()?.1 != 'u' {
+                                return None;
+                            }
+                            let low = parse_hex(&mut chars)?;
+                            if !(0xdc00..0xe000).contains(&low) {
+                                return None;
+                            }
+                            code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00);
+                        }
+                        text.push(char::from_u32(code)?);
+                    }
+                    escaped => text.push(escaped),
+                },
+                c => text.push(c),
+            }
+        }
+        None
+    }
+
+    pub fn eat(&mut self, c: char) -> bool {
+        self.input = self.input.trim_start();
+        match self.input.strip_prefix(c) {
+            Some(rest) => {
+                self.input = rest;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn expect(&mut self, c: char) -> Option<()> {
+        self.eat(c).then_some(())
+    }
+}
+
+fn parse_hex(chars: &mut impl Iterator<Item = (usize, char)>) -> Option<u32> {
+    let code: String = chars.take(4).map(|(_, c)| c).collect();
+    if code.len() != 4 {
+        return None;
+    }
+    u32::from_str_radix(&code, 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::JsonParser;
+
+    fn parse(input: &str) -> Option<String> {
+        JsonParser::new(input)
+            .parse_string()
+            .map(|text| text.into_owned())
+    }
+
+    #[test]
+    fn escapes() {
+        assert_eq!(parse(r#""plain""#).as_deref(), Some("plain"));
+        assert_eq!(parse(r#""a\"b\n\u003c\/""#).as_deref(), Some("a\"b\n</"));
+    }
+
+    #[test]
+    fn surrogate_pairs() {
+        assert_eq!(parse(r#""\ud83d\ude00""#).as_deref(), Some("\u{1f600}"));
+        assert_eq!(
+            parse(r#""impl \ud834\udd1e for T""#).as_deref(),
+            Some("impl \u{1d11e} for T")
+        );
+        assert_eq!(parse(r#""\ud83d""#), None);
+        assert_eq!(parse(r#""\ud83d\u0041""#), None);
+        assert_eq!(parse(r#""\ude00""#), None);
+    }
+
+    #[test]
+    fn string_maps() {
+        let entries = JsonParser::new(r#"{"Iter<'_, T>": "<h3>\ud83d\ude00</h3>", "b": ""}"#)
+            .parse_string_map()
+            .unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "Iter<'_, T>");
+        assert_eq!(entries[0].1, "<h3>\u{1f600}</h3>");
+    }
+}
@@ -0,0 +1,138 @@
+use std::fmt;
+
+use crate::atom::{plain_text, TextPart};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ItemKind {
+    Crate,
+    Module,
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    TraitAlias,
+    Function,
+    Macro,
+    Constant,
+    Static,
+    TypeAlias,
+    Primitive,
+    Keyword,
+    AttributeMacro,
+    DeriveMacro,
+    ForeignType,
+}
+
+const TITLE_PREFIXES: &[(&str, ItemKind)] = &[
+    ("Crate", ItemKind::Crate),
+    ("Module", ItemKind::Module),
+    ("Struct", ItemKind::Struct),
+    ("Enum", ItemKind::Enum),
+    ("Union", ItemKind::Union),
+    ("Trait Alias", ItemKind::TraitAlias),
+    ("Trait", ItemKind::Trait),
+    ("Function", ItemKind::Function),
+    ("Macro", ItemKind::Macro),
+    ("Constant", ItemKind::Constant),
+    ("Static", ItemKind::Static),
+    ("Type Alias", ItemKind::TypeAlias),
+    ("Type Definition", ItemKind::TypeAlias),
+    ("Primitive Type", ItemKind::Primitive),
+    ("Keyword", ItemKind::Keyword),
+    ("Attribute Macro", ItemKind::AttributeMacro),
+    ("Derive Macro", ItemKind::DeriveMacro),
+    ("Foreign Type", ItemKind::ForeignType),
+];
+
+// The prefix rustdoc puts before an item's name in its file name, e.g. `struct.Sleep.html`,
+// which is also the key of its group in the sidebar items script.
+const URL_PREFIXES: &[(&str, ItemKind)] = &[
+    ("mod", ItemKind::Module),
+    ("struct", ItemKind::Struct),
+    ("enum", ItemKind::Enum),
+    ("union", ItemKind::Union),
+    ("trait", ItemKind::Trait),
+    ("traitalias", ItemKind::TraitAlias),
+    ("fn", ItemKind::Function),
+    ("macro", ItemKind::Macro),
+    ("constant", ItemKind::Constant),
+    ("static", ItemKind::Static),
+    ("type", ItemKind::TypeAlias),
+    ("primitive", ItemKind::Primitive),
+    ("keyword", ItemKind::Keyword),
+    ("attr", ItemKind::AttributeMacro),
+    ("derive", ItemKind::DeriveMacro),
+    ("foreigntype", ItemKind::ForeignType),
+];
+
+impl ItemKind {
+    pub fn from_url_prefix(prefix: &str) -> Option<ItemKind> {
+        URL_PREFIXES
+            .iter()
+            .find(|(candidate, _)| *candidate == prefix)
+            .map(|(_, kind)| *kind)
+    }
+
+    pub fn title_prefix(&self) -> &'static str {
+        TITLE_PREFIXES
+            .iter()
+            .find(|(_, kind)| kind == self)
+            .map(|(prefix, _)| *prefix)
+            .unwrap()
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.title_prefix())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Path {
+    pub crate_name: String,
+    pub segments: Vec<String>,
+}
+
+impl Path {
+    pub fn name(&self) -> &str {
+        self.segments.last().unwrap_or(&self.crate_name)
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.crate_name)?;
+        for segment in &self.segments {
+            write!(f, "::{}", segment)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn parse_title(title: &[TextPart]) -> Option<(ItemKind, Vec<String>)> {
+    let title = plain_text(title);
+    let title = title.trim();
+
+    let (kind, rest) = TITLE_PREFIXES.iter().find_map(|(prefix, kind)| {
+        let rest = title.strip_prefix(prefix)?;
+        rest.starts_with(char::is_whitespace)
+            .then(|| (*kind, rest.trim()))
+    })?;
+
+    let segments: Vec<String> = rest
+        .split("::")
+        .map(|segment| segment.trim().to_string())
+        .collect();
+    if segments.iter().any(String::is_empty) {
+        return None;
+    }
+
+    Some((kind, segments))
+}
@@ -1,65 +1,271 @@
+mod anchor;
 mod atom;
+mod diagnostics;
+mod doctest;
+mod error;
 mod header;
 mod item;
+mod json;
+mod kind;
+mod profile;
+mod sidebar;
+mod source;
+mod syntax;
+mod trait_info;
+mod url;
+mod version;
 
-use atom::parse_pre;
-use header::{parse_fqn, parse_item_decl, parse_item_info, parse_top_doc};
-use item::{
-    parse_item_header,
-    table::{parse_block_table, parse_item_table},
-};
-use scraper::Selector;
+use std::borrow::Cow;
+
+use anchor::{find_impl, find_item, find_row, find_section, parse_anchor};
+use atom::{into_owned, into_owned_parts, parse_pre};
+use header::{parse_heading, parse_item_decl, parse_item_info, parse_top_doc, Fqn};
+use item::{parse_item_header, table::parse_table};
+use kind::parse_title;
+use profile::Profile;
+use scraper::{ElementRef, Selector};
+use sidebar::parse_sidebar;
 
 use crate::{
     header::parse_doc_block,
     item::{
-        fields::parse_struct_field_or_variant,
-        impls::{parse_impl_div, parse_impl_heading, parse_impl_items, parse_implementor_or_empty},
+        fields::{parse_struct_field_or_variant, parse_variants},
+        impls::{
+            parse_impl_category, parse_impl_div, parse_impl_heading, parse_impl_items,
+            parse_implementor_or_empty, parse_notable_traits_data, parse_trait_items,
+        },
         is_item_header,
     },
 };
 
-pub use atom::{Details, Paragraph, TextPart, TextStyle};
-pub use header::{ItemInfo, Section};
-pub use item::{Impl, Item, ItemRow};
+pub use anchor::AnchorTarget;
+pub use atom::{
+    parse_inline, Alignment, CodeAttribute, CodeBlock, Definition, Details, FootnoteReference,
+    Image, Inline, List, Paragraph, Table, TextPart, TextStyle, TokenKind,
+};
+pub use diagnostics::{Diagnostics, SkippedNode};
+pub use doctest::write_doctests;
+pub use error::{Expected, NodePath, ParseError, Stage};
+pub use header::{Footnote, ItemInfo, Section};
+pub use item::{impls::ImplCategory, Impl, Item, ItemRow};
+pub use kind::{ItemKind, Path};
+pub use sidebar::{
+    parse_sidebar_items, Sibling, SiblingGroup, SiblingKind, Sidebar, SidebarLink, SidebarSection,
+};
+pub use source::{parse_source_href, SourceLocation};
+pub use syntax::{
+    declaration::{
+        parse_declaration, parse_trait_item, Declaration, EnumDecl, Field, Fields, StructDecl,
+        TraitDecl, TraitItem, TypeAliasDecl, Variant,
+    },
+    impl_header::{parse_impl_header, ImplHeader},
+    signature::{parse_signature, Param, Qualifiers, Receiver, Signature},
+    Bound, FnPointer, GenericArg, GenericArgs, GenericParam, PathSegment, Type, TypePath,
+    WherePredicate,
+};
+pub use trait_info::{AssociatedItem, TraitInfo};
+pub use version::{detect_version, Channel, RustdocVersion};
 
 pub use scraper::Html;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Document<'a> {
+    pub version: Option<RustdocVersion>,
     pub title: Vec<TextPart<'a>>,
-    pub since: Option<&'a str>,
+    pub since: Option<Cow<'a, str>>,
     pub declaration: Option<Vec<TextPart<'a>>>,
     pub info: ItemInfo<'a>,
     pub description: Vec<Section<'a>>,
     pub items: Vec<ItemListing<'a>>,
+    pub sidebar: Sidebar<'a>,
+    pub source: Option<SourceLocation<'a>>,
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ItemListing<'a> {
     pub heading: Vec<TextPart<'a>>,
+    pub anchor: Option<Cow<'a, str>>,
+    /// Read from the heading `id`; `None` for listings that are not impls.
+    pub category: Option<ImplCategory>,
     pub kind: ListingType<'a>,
 }
 
 #[derive(Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
 pub enum ListingType<'a> {
     Table(Vec<ItemRow<'a>>),
     Fields(Vec<Item<'a>>),
     Impls(Vec<Impl<'a>>),
 }
 
-pub fn parse_document(html: &Html) -> Option<Document> {
-    let select_main = Selector::parse("#main").unwrap();
-    let main = html.select(&select_main).next()?;
+impl<'a> Document<'a> {
+    pub fn kind(&self) -> Option<ItemKind> {
+        parse_title(&self.title).map(|(kind, _)| kind)
+    }
+
+    pub fn path(&self) -> Option<Path> {
+        let (kind, mut segments) = parse_title(&self.title)?;
+        let crate_name = match kind {
+            ItemKind::Primitive | ItemKind::Keyword => {
+                self.sidebar.crate_name.as_deref()?.to_string()
+            }
+            _ => segments.remove(0),
+        };
+        Some(Path {
+            crate_name,
+            segments,
+        })
+    }
+
+    /// Parses the declaration block: struct and union fields, enum variants, trait supertraits
+    /// and associated items, type aliases, or a function signature.
+    pub fn parse_declaration(&self) -> Option<Declaration<'a>> {
+        parse_declaration(self.declaration.as_deref()?)
+    }
+
+    /// Finds what a URL fragment such as `method.iter`, `impl-Debug` or `#fn1` points to.
+    pub fn find_by_anchor(&self, anchor: &str) -> Option<AnchorTarget<'_, 'a>> {
+        let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+        find_section(&self.description, anchor).or_else(|| {
+            self.items
+                .iter()
+                .find_map(|listing| listing.find_by_anchor(anchor))
+        })
+    }
+
+    pub fn into_owned(self) -> Document<'static> {
+        Document {
+            version: self.version,
+            title: into_owned_parts(self.title),
+            since: self.since.map(into_owned),
+            declaration: self.declaration.map(into_owned_parts),
+            info: self.info.into_owned(),
+            description: self
+                .description
+                .into_iter()
+                .map(Section::into_owned)
+                .collect(),
+            items: self
+                .items
+                .into_iter()
+                .map(ItemListing::into_owned)
+                .collect(),
+            sidebar: self.sidebar.into_owned(),
+            source: self.source.map(SourceLocation::into_owned),
+        }
+    }
+}
+
+impl<'a> ItemListing<'a> {
+    fn find_by_anchor(&self, anchor: &str) -> Option<AnchorTarget<'_, 'a>> {
+        if self.anchor.as_deref() == Some(anchor) {
+            Some(AnchorTarget::Listing(self))
+        } else {
+            self.kind.find_by_anchor(anchor)
+        }
+    }
+
+    pub fn into_owned(self) -> ItemListing<'static> {
+        ItemListing {
+            heading: into_owned_parts(self.heading),
+            anchor: self.anchor.map(into_owned),
+            category: self.category,
+            kind: self.kind.into_owned(),
+        }
+    }
+}
+
+impl<'a> ListingType<'a> {
+    fn find_by_anchor(&self, anchor: &str) -> Option<AnchorTarget<'_, 'a>> {
+        match self {
+            ListingType::Table(rows) => find_row(rows, anchor),
+            ListingType::Fields(items) => find_item(items, anchor),
+            ListingType::Impls(impls) => find_impl(impls, anchor),
+        }
+    }
+
+    pub fn into_owned(self) -> ListingType<'static> {
+        match self {
+            ListingType::Table(rows) => {
+                ListingType::Table(rows.into_iter().map(ItemRow::into_owned).collect())
+            }
+            ListingType::Fields(items) => {
+                ListingType::Fields(items.into_iter().map(Item::into_owned).collect())
+            }
+            ListingType::Impls(impls) => {
+                ListingType::Impls(impls.into_iter().map(Impl::into_owned).collect())
+            }
+        }
+    }
+}
+
+pub fn parse_document(html: &Html) -> Result<Document<'_>, ParseError> {
+    parse_document_with_diagnostics(html, &mut Diagnostics::new())
+}
+
+fn parse_main<'a>(
+    html: &'a Html,
+    profile: &Profile,
+) -> Result<(ElementRef<'a>, Fqn<'a>), ParseError> {
+    let select_main = Selector::parse(profile.main).unwrap();
+    let main = html.select(&select_main).next().ok_or_else(|| {
+        ParseError::new(Stage::Main, Expected::Child(profile.main), html.tree.root())
+    })?;
+
+    let maybe_fqn = main
+        .children()
+        .find(|child| child.value().is_element())
+        .ok_or_else(|| {
+            ParseError::new(
+                Stage::Main,
+                Expected::Child(profile.heading.selector()),
+                *main,
+            )
+        })?;
+    let fqn = parse_heading(maybe_fqn, profile)?;
+
+    Ok((main, fqn))
+}
+
+pub fn parse_document_with_diagnostics<'a>(
+    html: &'a Html,
+    diagnostics: &mut Diagnostics,
+) -> Result<Document<'a>, ParseError> {
+    let version = detect_version(html);
+    let notable_traits = parse_notable_traits_data(html);
 
-    let mut children = main.children();
+    let mut first_error = None;
+    let (profile, main, fqn) = Profile::for_version(version.as_ref())
+        .fallbacks()
+        .find_map(|profile| match parse_main(html, &profile) {
+            Ok((main, fqn)) => Some((profile, main, fqn)),
+            Err(error) => {
+                first_error.get_or_insert(error);
+                None
+            }
+        })
+        .ok_or_else(|| first_error.unwrap())?;
 
-    let maybe_fqn = children.next()?;
-    let fqn = parse_fqn(maybe_fqn)?;
+    let mut children = main.children().filter(|child| child.value().is_element());
+    children.next();
 
     let maybe_decl = children.next();
-    let item_decl = maybe_decl.and_then(parse_item_decl).map(|decl| decl.code);
-    let pre = maybe_decl.and_then(parse_pre).map(|decl| decl.code);
+    let item_decl = maybe_decl
+        .and_then(|decl| {
+            profile
+                .fallbacks()
+                .find_map(|profile| parse_item_decl(decl, &profile).ok())
+        })
+        .map(|decl| decl.code);
+    let pre = maybe_decl
+        .and_then(|decl| parse_pre(decl).ok())
+        .map(|decl| decl.code);
     let declaration = item_decl.or(pre);
 
     let maybe_item_info = if declaration.is_none() {
@@ -67,7 +273,7 @@ pub fn parse_document(html: &Html) -> Option<Document> {
     } else {
         children.next()
     };
-    let item_info = maybe_item_info.and_then(parse_item_info);
+    let item_info = maybe_item_info.and_then(|info| parse_item_info(info).ok());
 
     let maybe_top_doc = if item_info.is_none() {
         maybe_item_info
@@ -75,13 +281,15 @@ pub fn parse_document(html: &Html) -> Option<Document> {
         children.next()
     };
     let doc_block = maybe_top_doc
-        .and_then(parse_top_doc)
+        .and_then(|top_doc| parse_top_doc(top_doc, diagnostics).ok())
         .map(|top_doc| top_doc.doc_block)
-        .or_else(|| maybe_top_doc.and_then(parse_doc_block));
+        .or_else(|| {
+            maybe_top_doc.and_then(|doc_block| parse_doc_block(doc_block, diagnostics).ok())
+        });
 
     let mut children = doc_block
         .is_none()
-        .then(|| maybe_top_doc)
+        .then_some(maybe_top_doc)
         .into_iter()
         .flatten()
         .chain(children)
@@ -89,112 +297,188 @@ pub fn parse_document(html: &Html) -> Option<Document> {
 
     let mut listings = vec![];
     while let Some(maybe_heading) = children.next() {
-        if let Some(heading) = parse_item_header(maybe_heading) {
+        if let Ok(heading) = parse_item_header(maybe_heading, &profile) {
+            let anchor = parse_anchor(maybe_heading);
+            let category = parse_impl_category(maybe_heading, &heading);
             while let Some(maybe_content) = children.peek() {
-                if is_item_header(*maybe_content) {
+                if is_item_header(*maybe_content, &profile) {
                     break;
-                } else if let Some(table) =
-                    parse_item_table(*maybe_content).or_else(|| parse_block_table(*maybe_content))
-                {
+                } else if let Ok(table) = parse_table(*maybe_content, &profile) {
                     children.next();
                     listings.push(ItemListing {
                         heading,
+                        anchor,
+                        category: None,
                         kind: ListingType::Table(table),
                     });
                     break;
-                } else if let Some(field) = parse_struct_field_or_variant(*maybe_content) {
+                } else if let Ok(field) = parse_struct_field_or_variant(*maybe_content) {
                     let mut items = vec![Item {
                         name: field,
                         info: Default::default(),
                         description: None,
+                        source: None,
+                        anchor: parse_anchor(*maybe_content),
+                        notable_traits: vec![],
                     }];
                     children.next();
                     while let Some(sibling) = children.peek() {
-                        if is_item_header(*sibling) {
+                        if is_item_header(*sibling, &profile) {
                             break;
-                        } else if let Some(field) = parse_struct_field_or_variant(*sibling) {
+                        } else if let Ok(field) = parse_struct_field_or_variant(*sibling) {
                             items.push(Item {
                                 name: field,
                                 info: Default::default(),
                                 description: None,
+                                source: None,
+                                anchor: parse_anchor(*sibling),
+                                notable_traits: vec![],
                             });
-                        } else if let Some(item_info) = parse_item_info(*sibling) {
+                        } else if let Ok(item_info) = parse_item_info(*sibling) {
                             if let Some(last_item) = items.last_mut() {
                                 last_item.info = item_info;
                             }
-                        } else if let Some(description) = parse_doc_block(*sibling) {
+                        } else if let Ok(description) = parse_doc_block(*sibling, diagnostics) {
                             if let Some(last_item) = items.last_mut() {
                                 last_item.description = Some(description.sections);
                             }
+                        } else {
+                            diagnostics.skip(Stage::Main, *sibling);
                         }
                         children.next();
                     }
                     listings.push(ItemListing {
                         heading,
+                        anchor,
+                        category: None,
                         kind: ListingType::Fields(items),
                     });
                     break;
-                } else if let Some(impl_heading) = parse_impl_heading(*maybe_content) {
-                    let mut impls = vec![Impl {
-                        target: impl_heading.title,
-                        items: vec![],
-                    }];
+                } else if let Ok(variants) = parse_variants(*maybe_content, diagnostics) {
+                    children.next();
+                    listings.push(ItemListing {
+                        heading,
+                        anchor,
+                        category: None,
+                        kind: ListingType::Fields(variants),
+                    });
+                    break;
+                } else if let Ok(impl_heading) = parse_impl_heading(*maybe_content) {
+                    let mut impls = vec![Impl::new(
+                        impl_heading.title,
+                        vec![],
+                        impl_heading.source,
+                        impl_heading.anchor,
+                    )];
                     children.next();
                     while let Some(sibling) = children.peek() {
-                        if is_item_header(*sibling) {
+                        if is_item_header(*sibling, &profile) {
                             break;
-                        } else if let Some(impl_items) = parse_impl_items(*sibling) {
+                        } else if let Ok(impl_items) =
+                            parse_impl_items(*sibling, &notable_traits, diagnostics)
+                        {
                             if let Some(last_impl) = impls.last_mut() {
                                 last_impl.items = impl_items;
                             }
-                        } else if let Some(impl_heading) = parse_impl_heading(*sibling) {
-                            impls.push(Impl {
-                                target: impl_heading.title,
-                                items: vec![],
-                            });
+                        } else if let Ok(impl_heading) = parse_impl_heading(*sibling) {
+                            impls.push(Impl::new(
+                                impl_heading.title,
+                                vec![],
+                                impl_heading.source,
+                                impl_heading.anchor,
+                            ));
+                        } else {
+                            diagnostics.skip(Stage::Main, *sibling);
                         }
                         children.next();
                     }
                     listings.push(ItemListing {
                         heading,
+                        anchor,
+                        category,
                         kind: ListingType::Impls(impls),
                     });
                     break;
-                } else if let Some(implementor) = parse_implementor_or_empty(*maybe_content) {
+                } else if let Ok(implementor) =
+                    parse_implementor_or_empty(*maybe_content, &notable_traits, diagnostics)
+                {
                     let mut impls = vec![implementor];
                     children.next();
                     while let Some(sibling) = children.peek() {
-                        if is_item_header(*sibling) {
+                        if is_item_header(*sibling, &profile) {
                             break;
-                        } else if let Some(impl_or_empty) = parse_implementor_or_empty(*sibling) {
+                        } else if let Ok(impl_or_empty) =
+                            parse_implementor_or_empty(*sibling, &notable_traits, diagnostics)
+                        {
                             impls.push(impl_or_empty);
+                        } else {
+                            diagnostics.skip(Stage::Main, *sibling);
                         }
                         children.next();
                     }
                     listings.push(ItemListing {
                         heading,
+                        anchor,
+                        category,
                         kind: ListingType::Impls(impls),
                     });
                     break;
-                } else if let Some(impl_div) = parse_impl_div(*maybe_content) {
+                } else if let Ok(items) =
+                    parse_trait_items(*maybe_content, &notable_traits, diagnostics)
+                {
+                    children.next();
                     listings.push(ItemListing {
                         heading,
+                        anchor,
+                        category: None,
+                        kind: ListingType::Fields(items),
+                    });
+                    break;
+                } else if let Ok(impl_div) =
+                    parse_impl_div(*maybe_content, &notable_traits, diagnostics)
+                {
+                    children.next();
+                    listings.push(ItemListing {
+                        heading,
+                        anchor,
+                        category,
                         kind: ListingType::Impls(impl_div),
                     });
                     break;
                 } else {
+                    diagnostics.skip(Stage::Main, *maybe_content);
                     children.next();
                 }
             }
+        } else {
+            diagnostics.skip(Stage::Main, maybe_heading);
         }
     }
 
-    Some(Document {
+    let select_sidebar = Selector::parse("nav.sidebar").unwrap();
+    let mut sidebar = html
+        .select(&select_sidebar)
+        .find_map(|sidebar| parse_sidebar(*sidebar).ok())
+        .unwrap_or_default();
+    let select_items_src = Selector::parse("script[src*=\"sidebar-items\"]").unwrap();
+    sidebar.items_src = html
+        .select(&select_items_src)
+        .filter_map(|script| script.value().attr("src"))
+        .find(|src| {
+            let file = src.rsplit('/').next().unwrap_or_default();
+            file.starts_with("sidebar-items") && file.ends_with(".js")
+        })
+        .map(Cow::Borrowed);
+
+    Ok(Document {
+        version,
         title: fqn.title,
-        since: fqn.since,
+        since: fqn.since.map(Cow::Borrowed),
         declaration,
         info: item_info.unwrap_or_default(),
-        description: doc_block.map_or_else(|| vec![], |block| block.sections),
+        description: doc_block.map_or_else(Vec::new, |block| block.sections),
         items: listings,
+        sidebar,
+        source: fqn.source,
     })
 }
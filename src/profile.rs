@@ -0,0 +1,212 @@
+use ego_tree::NodeRef;
+use scraper::{node::Element, Node};
+use selectors::attr::CaseSensitivity;
+
+use crate::{
+    error::{Expected, ParseError, Stage},
+    version::{Channel, RustdocVersion},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    pub main: &'static str,
+    pub heading: HeadingLayout,
+    pub in_band: &'static str,
+    pub out_of_band: &'static str,
+    pub section_headers: &'static [&'static str],
+    pub item_decl: &'static str,
+    pub item_table: ItemTableLayout,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadingLayout {
+    Fqn,
+    MainHeading,
+}
+
+impl HeadingLayout {
+    pub(crate) fn selector(&self) -> &'static str {
+        match self {
+            HeadingLayout::Fqn => "h1.fqn",
+            HeadingLayout::MainHeading => "div.main-heading",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ItemTableLayout {
+    Block,
+    Rows(RowTable),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RowTable {
+    pub table: Tag,
+    pub row: Option<Tag>,
+    pub left: Tag,
+    pub right: Tag,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tag {
+    pub name: &'static str,
+    pub class: Option<&'static str>,
+    selector: &'static str,
+}
+
+// `Expected` holds `&'static str`, so the `name.class` selector is put together at compile time.
+macro_rules! tag {
+    ($name:literal) => {
+        Tag {
+            name: $name,
+            class: None,
+            selector: $name,
+        }
+    };
+    ($name:literal, $class:literal) => {
+        Tag {
+            name: $name,
+            class: Some($class),
+            selector: concat!($name, ".", $class),
+        }
+    };
+}
+
+impl Tag {
+    pub(crate) fn selector(&self) -> &'static str {
+        self.selector
+    }
+
+    pub(crate) fn expect<'a>(
+        &self,
+        node: NodeRef<'a, Node>,
+        stage: Stage,
+    ) -> Result<&'a Element, ParseError> {
+        let element = match node.value().as_element() {
+            Some(element) if element.name() == self.name => element,
+            _ => return Err(ParseError::new(stage, Expected::Element(self.name), node)),
+        };
+
+        match self.class {
+            Some(class) if !element.has_class(class, CaseSensitivity::CaseSensitive) => {
+                Err(ParseError::new(stage, Expected::Class(class), node))
+            }
+            _ => Ok(element),
+        }
+    }
+}
+
+const ITEM_LEFT_TABLE: RowTable = RowTable {
+    table: tag!("div", "item-table"),
+    row: Some(tag!("div", "item-row")),
+    left: tag!("div", "item-left"),
+    right: tag!("div", "item-right"),
+};
+
+const ITEM_NAME_TABLE: RowTable = RowTable {
+    table: tag!("div", "item-table"),
+    row: Some(tag!("div", "item-row")),
+    left: tag!("div", "item-name"),
+    right: tag!("div", "desc"),
+};
+
+const LIST_TABLE: RowTable = RowTable {
+    table: tag!("ul", "item-table"),
+    row: Some(tag!("li")),
+    left: tag!("div", "item-name"),
+    right: tag!("div", "desc"),
+};
+
+const DESCRIPTION_TABLE: RowTable = RowTable {
+    table: tag!("dl", "item-table"),
+    row: None,
+    left: tag!("dt"),
+    right: tag!("dd"),
+};
+
+// Minor versions where the layout changed, newest first.
+const LAYOUT_MINORS: &[u32] = &[85, 82, 74, 68, 62, 60, 57, 54, 0];
+
+impl Profile {
+    pub fn for_version(version: Option<&RustdocVersion>) -> Self {
+        let at_least = |minor| version.is_none_or(|version| version.at_least(1, minor));
+
+        let item_table = if at_least(85) {
+            ItemTableLayout::Rows(DESCRIPTION_TABLE)
+        } else if at_least(74) {
+            ItemTableLayout::Rows(LIST_TABLE)
+        } else if at_least(68) {
+            ItemTableLayout::Rows(ITEM_NAME_TABLE)
+        } else if at_least(54) {
+            ItemTableLayout::Rows(ITEM_LEFT_TABLE)
+        } else {
+            ItemTableLayout::Block
+        };
+
+        Profile {
+            main: if at_least(60) {
+                "#main-content"
+            } else {
+                "#main"
+            },
+            heading: if at_least(62) {
+                HeadingLayout::MainHeading
+            } else {
+                HeadingLayout::Fqn
+            },
+            in_band: "in-band",
+            out_of_band: if at_least(82) {
+                "sub-heading"
+            } else {
+                "out-of-band"
+            },
+            section_headers: &["section-header", "small-section-header"],
+            item_decl: if at_least(57) {
+                "item-decl"
+            } else {
+                "type-decl"
+            },
+            item_table,
+        }
+    }
+
+    pub(crate) fn fallbacks(self) -> impl Iterator<Item = Profile> {
+        let others = LAYOUT_MINORS.iter().filter_map(move |&minor| {
+            let profile = Profile::for_version(Some(&RustdocVersion {
+                major: 1,
+                minor,
+                patch: 0,
+                channel: Channel::Stable,
+                date: None,
+            }));
+            (profile != self).then_some(profile)
+        });
+        std::iter::once(self).chain(others)
+    }
+
+    pub(crate) fn is_section_header(&self, element: &Element) -> bool {
+        self.section_headers
+            .iter()
+            .any(|class| element.has_class(class, CaseSensitivity::CaseSensitive))
+    }
+}
+
+impl Default for Profile {
+    fn default() -> Self {
+        Self::for_version(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DESCRIPTION_TABLE, ITEM_LEFT_TABLE, LIST_TABLE};
+
+    #[test]
+    fn selectors_name_the_element_and_class() {
+        assert_eq!(ITEM_LEFT_TABLE.table.selector(), "div.item-table");
+        assert_eq!(ITEM_LEFT_TABLE.right.selector(), "div.item-right");
+        assert_eq!(LIST_TABLE.table.selector(), "ul.item-table");
+        assert_eq!(LIST_TABLE.row.unwrap().selector(), "li");
+        assert_eq!(DESCRIPTION_TABLE.left.selector(), "dt");
+    }
+}
@@ -0,0 +1,380 @@
+use std::borrow::Cow;
+
+use ego_tree::NodeRef;
+use scraper::{ElementRef, Node};
+use selectors::attr::CaseSensitivity;
+
+use crate::{
+    atom::{into_owned, into_owned_parts, parse_text_inside, TextPart},
+    error::{expect_element, Expected, ParseError, Stage},
+    json::JsonParser,
+    kind::ItemKind,
+};
+
+/// The crate name, version, section link lists and parent module link from the page's sidebar.
+/// The "Sections" table of contents of newer pages is left out, since its headings are already
+/// in `Document::description`.
+#[derive(Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sidebar<'a> {
+    pub crate_name: Option<Cow<'a, str>>,
+    pub version: Option<Cow<'a, str>>,
+    pub sections: Vec<SidebarSection<'a>>,
+    pub parent: Option<Vec<TextPart<'a>>>,
+    pub items_src: Option<Cow<'a, str>>,
+    /// Empty after `parse_document`, since rustdoc loads sibling items from the script at
+    /// `items_src`. Fill it with `parse_sidebar_items` on that script's contents.
+    pub siblings: Vec<SiblingGroup<'a>>,
+}
+
+impl Sidebar<'_> {
+    pub fn into_owned(self) -> Sidebar<'static> {
+        Sidebar {
+            crate_name: self.crate_name.map(into_owned),
+            version: self.version.map(into_owned),
+            sections: self
+                .sections
+                .into_iter()
+                .map(SidebarSection::into_owned)
+                .collect(),
+            parent: self.parent.map(into_owned_parts),
+            items_src: self.items_src.map(into_owned),
+            siblings: self
+                .siblings
+                .into_iter()
+                .map(SiblingGroup::into_owned)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SidebarSection<'a> {
+    pub title: Option<Vec<TextPart<'a>>>,
+    /// Without the leading `#`, so it can be passed to `Document::find_by_anchor`.
+    pub anchor: Option<Cow<'a, str>>,
+    pub links: Vec<SidebarLink<'a>>,
+}
+
+impl SidebarSection<'_> {
+    pub fn into_owned(self) -> SidebarSection<'static> {
+        SidebarSection {
+            title: self.title.map(into_owned_parts),
+            anchor: self.anchor.map(into_owned),
+            links: self
+                .links
+                .into_iter()
+                .map(SidebarLink::into_owned)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SidebarLink<'a> {
+    pub name: Vec<TextPart<'a>>,
+    pub href: Cow<'a, str>,
+}
+
+impl SidebarLink<'_> {
+    pub fn into_owned(self) -> SidebarLink<'static> {
+        SidebarLink {
+            name: into_owned_parts(self.name),
+            href: into_owned(self.href),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SiblingGroup<'a> {
+    pub kind: SiblingKind<'a>,
+    pub items: Vec<Sibling<'a>>,
+}
+
+impl SiblingGroup<'_> {
+    pub fn into_owned(self) -> SiblingGroup<'static> {
+        SiblingGroup {
+            kind: self.kind.into_owned(),
+            items: self.items.into_iter().map(Sibling::into_owned).collect(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum SiblingKind<'a> {
+    Known(ItemKind),
+    /// A group key with no `ItemKind`, kept as rustdoc wrote it.
+    Unknown(Cow<'a, str>),
+}
+
+impl SiblingKind<'_> {
+    pub fn into_owned(self) -> SiblingKind<'static> {
+        match self {
+            SiblingKind::Known(kind) => SiblingKind::Known(kind),
+            SiblingKind::Unknown(kind) => SiblingKind::Unknown(into_owned(kind)),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sibling<'a> {
+    pub name: Cow<'a, str>,
+    pub summary: Option<Cow<'a, str>>,
+}
+
+impl Sibling<'_> {
+    pub fn into_owned(self) -> Sibling<'static> {
+        Sibling {
+            name: into_owned(self.name),
+            summary: self.summary.map(into_owned),
+        }
+    }
+}
+
+pub fn parse_sidebar(maybe_sidebar: NodeRef<Node>) -> Result<Sidebar, ParseError> {
+    let sidebar = expect_element(maybe_sidebar, Stage::Sidebar, "nav")?;
+
+    if !sidebar.has_class("sidebar", CaseSensitivity::CaseSensitive) {
+        return Err(ParseError::new(
+            Stage::Sidebar,
+            Expected::Class("sidebar"),
+            maybe_sidebar,
+        ));
+    }
+
+    let mut result = Sidebar::default();
+    parse_sidebar_children(maybe_sidebar, &mut result);
+
+    Ok(result)
+}
+
+fn parse_sidebar_children<'a>(node: NodeRef<'a, Node>, sidebar: &mut Sidebar<'a>) {
+    for child in node.children() {
+        let element = match child.value().as_element() {
+            Some(element) => element,
+            None => continue,
+        };
+
+        if has_class(child, "sidebar-crate") {
+            // The logo link comes first but has no text.
+            sidebar.crate_name = child
+                .descendants()
+                .filter(|child| is_element(*child, "a"))
+                .find_map(text_of);
+            sidebar.version = child
+                .descendants()
+                .find(|child| has_class(*child, "version"))
+                .and_then(text_of);
+        } else if has_class(child, "version") && sidebar.version.is_none() {
+            sidebar.version = child
+                .descendants()
+                .find(|child| is_element(*child, "p"))
+                .and_then(text_of)
+                .map(strip_version_prefix);
+        } else if element.name() == "a" && sidebar.crate_name.is_none() && is_logo_link(child) {
+            sidebar.crate_name = element
+                .attr("href")
+                .and_then(crate_of_href)
+                .map(Cow::Borrowed);
+        } else if has_class(child, "sidebar-elems") {
+            parse_sidebar_elems(child, sidebar);
+        } else {
+            parse_sidebar_children(child, sidebar);
+        }
+    }
+}
+
+fn parse_sidebar_elems<'a>(node: NodeRef<'a, Node>, sidebar: &mut Sidebar<'a>) {
+    let mut in_page_toc = false;
+    for child in node.children() {
+        let element = match child.value().as_element() {
+            Some(element) => element,
+            None => continue,
+        };
+
+        if element.name() == "h3" || has_class(child, "sidebar-title") {
+            let anchor = if element.name() == "a" {
+                element.attr("href")
+            } else {
+                child
+                    .descendants()
+                    .find_map(|link| link.value().as_element()?.attr("href"))
+            };
+            // Newer rustdoc lists the headings of the page's own docs under a "Sections" title
+            // linking to `#`; those are already in `Document::description`.
+            in_page_toc = anchor == Some("#");
+            if in_page_toc {
+                continue;
+            }
+            sidebar.sections.push(SidebarSection {
+                title: Some(parse_text_inside(child)),
+                anchor: anchor.map(|anchor| Cow::Borrowed(anchor.trim_start_matches('#'))),
+                links: vec![],
+            });
+        } else if element.name() == "ul" || has_class(child, "sidebar-links") {
+            if std::mem::take(&mut in_page_toc) {
+                continue;
+            }
+            let links = child
+                .descendants()
+                .filter_map(|link| {
+                    let href = link.value().as_element()?.attr("href")?;
+                    Some(SidebarLink {
+                        name: parse_text_inside(link),
+                        href: Cow::Borrowed(href),
+                    })
+                })
+                .collect();
+            match sidebar.sections.last_mut() {
+                Some(section) if section.links.is_empty() => section.links = links,
+                _ => sidebar.sections.push(SidebarSection {
+                    title: None,
+                    anchor: None,
+                    links,
+                }),
+            }
+        } else if (element.name() == "h2" || has_class(child, "location")) && is_parent_link(child)
+        {
+            sidebar.parent = Some(parse_text_inside(child));
+        } else if element.name() != "script" {
+            parse_sidebar_elems(child, sidebar);
+        }
+    }
+}
+
+fn is_element(node: NodeRef<Node>, name: &str) -> bool {
+    node.value()
+        .as_element()
+        .is_some_and(|element| element.name() == name)
+}
+
+fn has_class(node: NodeRef<Node>, class: &str) -> bool {
+    node.value()
+        .as_element()
+        .is_some_and(|element| element.has_class(class, CaseSensitivity::CaseSensitive))
+}
+
+fn text_of(node: NodeRef<Node>) -> Option<Cow<str>> {
+    let element = ElementRef::wrap(node)?;
+    let mut texts = element.text();
+    let first = texts.next()?;
+    match texts.next() {
+        None => Some(Cow::Borrowed(first.trim())),
+        Some(second) => {
+            let mut text = first.to_string();
+            text.push_str(second);
+            texts.for_each(|rest| text.push_str(rest));
+            Some(Cow::Owned(text.trim().to_string()))
+        }
+    }
+}
+
+fn strip_version_prefix(version: Cow<str>) -> Cow<str> {
+    match version {
+        Cow::Borrowed(version) => {
+            Cow::Borrowed(version.strip_prefix("Version ").unwrap_or(version))
+        }
+        Cow::Owned(version) => match version.strip_prefix("Version ") {
+            Some(stripped) => Cow::Owned(stripped.to_string()),
+            None => Cow::Owned(version),
+        },
+    }
+}
+
+fn is_logo_link(node: NodeRef<Node>) -> bool {
+    has_class(node, "logo-container")
+        || node
+            .children()
+            .any(|child| has_class(child, "logo-container"))
+}
+
+fn crate_of_href(href: &str) -> Option<&str> {
+    let mut segments = href.strip_suffix("/index.html")?.rsplit('/');
+    segments
+        .next()
+        .filter(|name| !name.is_empty() && *name != "..")
+}
+
+fn is_parent_link(node: NodeRef<Node>) -> bool {
+    node.descendants().any(|link| {
+        link.value()
+            .as_element()
+            .and_then(|element| element.attr("href"))
+            .is_some_and(|href| href != "#")
+    })
+}
+
+pub fn parse_sidebar_items(script: &str) -> Option<Vec<SiblingGroup<'_>>> {
+    let start = script.find('{')?;
+    parse_groups(&mut JsonParser::new(&script[start..]))
+}
+
+fn parse_groups<'a>(parser: &mut JsonParser<'a>) -> Option<Vec<SiblingGroup<'a>>> {
+    let mut groups = vec![];
+
+    parser.expect('{')?;
+    if parser.eat('}') {
+        return Some(groups);
+    }
+    loop {
+        let kind = parser.parse_string()?;
+        parser.expect(':')?;
+        groups.push(SiblingGroup {
+            kind: match ItemKind::from_url_prefix(&kind) {
+                Some(kind) => SiblingKind::Known(kind),
+                None => SiblingKind::Unknown(kind),
+            },
+            items: parse_siblings(parser)?,
+        });
+        if !parser.eat(',') {
+            break;
+        }
+    }
+    parser.expect('}')?;
+
+    Some(groups)
+}
+
+fn parse_siblings<'a>(parser: &mut JsonParser<'a>) -> Option<Vec<Sibling<'a>>> {
+    let mut items = vec![];
+
+    parser.expect('[')?;
+    if parser.eat(']') {
+        return Some(items);
+    }
+    loop {
+        if parser.eat('[') {
+            let name = parser.parse_string()?;
+            let summary = if parser.eat(',') {
+                Some(parser.parse_string()?).filter(|summary| !summary.is_empty())
+            } else {
+                None
+            };
+            while parser.eat(',') {
+                parser.parse_string()?;
+            }
+            parser.expect(']')?;
+            items.push(Sibling { name, summary });
+        } else {
+            items.push(Sibling {
+                name: parser.parse_string()?,
+                summary: None,
+            });
+        }
+        if !parser.eat(',') {
+            break;
+        }
+    }
+    parser.expect(']')?;
+
+    Some(items)
+}
@@ -0,0 +1,154 @@
+use std::borrow::Cow;
+
+use ego_tree::NodeRef;
+use scraper::Node;
+use selectors::attr::CaseSensitivity;
+
+use crate::atom::into_owned;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SourceLocation<'a> {
+    pub href: Cow<'a, str>,
+    pub crate_name: Cow<'a, str>,
+    pub file: Cow<'a, str>,
+    pub lines: Option<(u32, u32)>,
+}
+
+impl SourceLocation<'_> {
+    pub fn into_owned(self) -> SourceLocation<'static> {
+        SourceLocation {
+            href: into_owned(self.href),
+            crate_name: into_owned(self.crate_name),
+            file: into_owned(self.file),
+            lines: self.lines,
+        }
+    }
+}
+
+pub fn parse_source_href(href: &str) -> Option<SourceLocation<'_>> {
+    let (path, fragment) = match href.split_once('#') {
+        Some((path, fragment)) => (path, Some(fragment)),
+        None => (href, None),
+    };
+
+    // Split on the first `src/` after the doc root, since the file itself may sit in a `src`
+    // directory of the crate.
+    let mut path = match path.split_once("://") {
+        Some((_, rest)) => rest.split_once('/')?.1,
+        None => path.trim_start_matches('/'),
+    };
+    while let Some(rest) = path.strip_prefix("../").or_else(|| path.strip_prefix("./")) {
+        path = rest;
+    }
+    let path = match path.strip_prefix("src/") {
+        Some(path) => path,
+        None => &path[path.find("/src/")? + "/src/".len()..],
+    };
+    let (crate_name, file) = path.strip_suffix(".html")?.split_once('/')?;
+
+    let lines = fragment.and_then(|fragment| {
+        let (start, end) = fragment.split_once('-').unwrap_or((fragment, fragment));
+        Some((start.parse().ok()?, end.parse().ok()?))
+    });
+
+    Some(SourceLocation {
+        href: Cow::Borrowed(href),
+        crate_name: Cow::Borrowed(crate_name),
+        file: Cow::Borrowed(file),
+        lines,
+    })
+}
+
+pub fn find_source_link(node: NodeRef<Node>) -> Option<SourceLocation> {
+    node.descendants().find_map(|child| {
+        let element = child.value().as_element()?;
+        if element.name() == "a"
+            && (element.has_class("srclink", CaseSensitivity::CaseSensitive)
+                || element.has_class("src", CaseSensitivity::CaseSensitive))
+        {
+            parse_source_href(element.attr("href")?)
+        } else {
+            None
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_source_href;
+
+    fn check(href: &str, crate_name: &str, file: &str, lines: Option<(u32, u32)>) {
+        let location = parse_source_href(href).unwrap();
+        assert_eq!(location.href, href);
+        assert_eq!(location.crate_name, crate_name, "crate of {:?}", href);
+        assert_eq!(location.file, file, "file of {:?}", href);
+        assert_eq!(location.lines, lines, "lines of {:?}", href);
+    }
+
+    #[test]
+    fn plain_hrefs() {
+        check(
+            "../src/core/option.rs.html#563",
+            "core",
+            "option.rs",
+            Some((563, 563)),
+        );
+        check(
+            "../../src/std/io/pipe.rs.html#5",
+            "std",
+            "io/pipe.rs",
+            Some((5, 5)),
+        );
+        check(
+            "https://doc.rust-lang.org/nightly/src/core/any.rs.html#133",
+            "core",
+            "any.rs",
+            Some((133, 133)),
+        );
+    }
+
+    #[test]
+    fn ranged_hrefs() {
+        check(
+            "../src/core/slice/mod.rs.html#12-34",
+            "core",
+            "slice/mod.rs",
+            Some((12, 34)),
+        );
+        check(
+            "https://docs.rs/teloxide-core/0.4.5/src/teloxide_core/types/reply_markup.rs.html#10-20",
+            "teloxide_core",
+            "types/reply_markup.rs",
+            Some((10, 20)),
+        );
+    }
+
+    #[test]
+    fn hrefs_without_fragment() {
+        check("../src/alloc/slice.rs.html", "alloc", "slice.rs", None);
+        check("src/tokio/time/mod.rs.html", "tokio", "time/mod.rs", None);
+    }
+
+    #[test]
+    fn nested_src_directories() {
+        check(
+            "../../src/mycrate/foo/src/bar.rs.html#3",
+            "mycrate",
+            "foo/src/bar.rs",
+            Some((3, 3)),
+        );
+        check(
+            "https://docs.rs/mycrate/1.0.0/src/mycrate/src/lib.rs.html",
+            "mycrate",
+            "src/lib.rs",
+            None,
+        );
+    }
+
+    #[test]
+    fn rejects_other_hrefs() {
+        assert_eq!(parse_source_href("../core/option/enum.Option.html"), None);
+        assert_eq!(parse_source_href("../src/core/option.rs"), None);
+    }
+}
@@ -0,0 +1,879 @@
+use std::{borrow::Cow, fmt};
+
+use crate::atom::{into_owned, TextPart};
+
+use super::{
+    into_owned_bounds, into_owned_generics, into_owned_where_clause,
+    lexer::{tokenize, Kind},
+    parser::Parser,
+    signature::{parse_fn, parse_signature, Signature},
+    write_generics, write_separated, write_where_clause, Bound, GenericParam, Type, WherePredicate,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Declaration<'a> {
+    Struct(StructDecl<'a>),
+    Union(StructDecl<'a>),
+    Enum(EnumDecl<'a>),
+    Trait(TraitDecl<'a>),
+    TypeAlias(TypeAliasDecl<'a>),
+    Function(Signature<'a>),
+}
+
+impl Declaration<'_> {
+    pub fn into_owned(self) -> Declaration<'static> {
+        match self {
+            Declaration::Struct(decl) => Declaration::Struct(decl.into_owned()),
+            Declaration::Union(decl) => Declaration::Union(decl.into_owned()),
+            Declaration::Enum(decl) => Declaration::Enum(decl.into_owned()),
+            Declaration::Trait(decl) => Declaration::Trait(decl.into_owned()),
+            Declaration::TypeAlias(decl) => Declaration::TypeAlias(decl.into_owned()),
+            Declaration::Function(signature) => Declaration::Function(signature.into_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StructDecl<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub generics: Vec<GenericParam<'a>>,
+    pub fields: Fields<'a>,
+    // Whether rustdoc replaced private or hidden fields with a comment
+    pub fields_omitted: bool,
+    pub where_clause: Vec<WherePredicate<'a>>,
+}
+
+impl StructDecl<'_> {
+    pub fn into_owned(self) -> StructDecl<'static> {
+        StructDecl {
+            visibility: self.visibility.map(into_owned),
+            name: into_owned(self.name),
+            generics: into_owned_generics(self.generics),
+            fields: self.fields.into_owned(),
+            fields_omitted: self.fields_omitted,
+            where_clause: into_owned_where_clause(self.where_clause),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Fields<'a> {
+    Named(Vec<Field<'a>>),
+    Unnamed(Vec<Field<'a>>),
+    Unit,
+}
+
+impl Fields<'_> {
+    pub fn into_owned(self) -> Fields<'static> {
+        match self {
+            Fields::Named(fields) => {
+                Fields::Named(fields.into_iter().map(Field::into_owned).collect())
+            }
+            Fields::Unnamed(fields) => {
+                Fields::Unnamed(fields.into_iter().map(Field::into_owned).collect())
+            }
+            Fields::Unit => Fields::Unit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Field<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub name: Option<Cow<'a, str>>,
+    pub ty: Type<'a>,
+}
+
+impl Field<'_> {
+    pub fn into_owned(self) -> Field<'static> {
+        Field {
+            visibility: self.visibility.map(into_owned),
+            name: self.name.map(into_owned),
+            ty: self.ty.into_owned(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EnumDecl<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub generics: Vec<GenericParam<'a>>,
+    pub where_clause: Vec<WherePredicate<'a>>,
+    pub variants: Vec<Variant<'a>>,
+}
+
+impl EnumDecl<'_> {
+    pub fn into_owned(self) -> EnumDecl<'static> {
+        EnumDecl {
+            visibility: self.visibility.map(into_owned),
+            name: into_owned(self.name),
+            generics: into_owned_generics(self.generics),
+            where_clause: into_owned_where_clause(self.where_clause),
+            variants: self.variants.into_iter().map(Variant::into_owned).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Variant<'a> {
+    pub name: Cow<'a, str>,
+    pub fields: Fields<'a>,
+    pub discriminant: Option<Cow<'a, str>>,
+}
+
+impl Variant<'_> {
+    pub fn into_owned(self) -> Variant<'static> {
+        Variant {
+            name: into_owned(self.name),
+            fields: self.fields.into_owned(),
+            discriminant: self.discriminant.map(into_owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TraitDecl<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub unsafe_: bool,
+    pub auto: bool,
+    pub name: Cow<'a, str>,
+    pub generics: Vec<GenericParam<'a>>,
+    pub supertraits: Vec<Bound<'a>>,
+    pub where_clause: Vec<WherePredicate<'a>>,
+    pub items: Vec<TraitItem<'a>>,
+}
+
+impl TraitDecl<'_> {
+    pub fn into_owned(self) -> TraitDecl<'static> {
+        TraitDecl {
+            visibility: self.visibility.map(into_owned),
+            unsafe_: self.unsafe_,
+            auto: self.auto,
+            name: into_owned(self.name),
+            generics: into_owned_generics(self.generics),
+            supertraits: into_owned_bounds(self.supertraits),
+            where_clause: into_owned_where_clause(self.where_clause),
+            items: self.items.into_iter().map(TraitItem::into_owned).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum TraitItem<'a> {
+    Type {
+        name: Cow<'a, str>,
+        generics: Vec<GenericParam<'a>>,
+        bounds: Vec<Bound<'a>>,
+        where_clause: Vec<WherePredicate<'a>>,
+        default: Option<Type<'a>>,
+    },
+    Const {
+        name: Cow<'a, str>,
+        ty: Type<'a>,
+        default: Option<Cow<'a, str>>,
+    },
+    Fn {
+        signature: Signature<'a>,
+        provided: bool,
+    },
+}
+
+impl TraitItem<'_> {
+    pub fn into_owned(self) -> TraitItem<'static> {
+        match self {
+            TraitItem::Type {
+                name,
+                generics,
+                bounds,
+                where_clause,
+                default,
+            } => TraitItem::Type {
+                name: into_owned(name),
+                generics: into_owned_generics(generics),
+                bounds: into_owned_bounds(bounds),
+                where_clause: into_owned_where_clause(where_clause),
+                default: default.map(Type::into_owned),
+            },
+            TraitItem::Const { name, ty, default } => TraitItem::Const {
+                name: into_owned(name),
+                ty: ty.into_owned(),
+                default: default.map(into_owned),
+            },
+            TraitItem::Fn {
+                signature,
+                provided,
+            } => TraitItem::Fn {
+                signature: signature.into_owned(),
+                provided,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypeAliasDecl<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub name: Cow<'a, str>,
+    pub generics: Vec<GenericParam<'a>>,
+    pub where_clause: Vec<WherePredicate<'a>>,
+    pub ty: Type<'a>,
+}
+
+impl TypeAliasDecl<'_> {
+    pub fn into_owned(self) -> TypeAliasDecl<'static> {
+        TypeAliasDecl {
+            visibility: self.visibility.map(into_owned),
+            name: into_owned(self.name),
+            generics: into_owned_generics(self.generics),
+            where_clause: into_owned_where_clause(self.where_clause),
+            ty: self.ty.into_owned(),
+        }
+    }
+}
+
+pub fn parse_declaration<'a>(declaration: &[TextPart<'a>]) -> Option<Declaration<'a>> {
+    let mut parser = Parser::new(tokenize(declaration));
+
+    parser.skip_attributes();
+    let visibility = parser.parse_visibility();
+
+    let result = if parser.eat("struct") {
+        Declaration::Struct(parse_struct(&mut parser, visibility)?)
+    } else if parser.is("union") && parser.peek_nth(1).is_some_and(|t| t.kind == Kind::Ident) {
+        parser.next();
+        Declaration::Union(parse_struct(&mut parser, visibility)?)
+    } else if parser.eat("enum") {
+        Declaration::Enum(parse_enum(&mut parser, visibility)?)
+    } else if parser.eat("type") {
+        Declaration::TypeAlias(parse_type_alias(&mut parser, visibility)?)
+    } else if parser.is("trait") || parser.is("auto") || parser.is("unsafe") && is_trait(&parser) {
+        Declaration::Trait(parse_trait(&mut parser, visibility)?)
+    } else {
+        return parse_signature(declaration).map(Declaration::Function);
+    };
+
+    parser.eat(";");
+    parser.is_end().then_some(result)
+}
+
+fn is_trait(parser: &Parser) -> bool {
+    (1..=2).any(|n| {
+        parser
+            .peek_nth(n)
+            .is_some_and(|token| token.text == "trait")
+    })
+}
+
+fn parse_struct<'a>(
+    parser: &mut Parser<'a>,
+    visibility: Option<Cow<'a, str>>,
+) -> Option<StructDecl<'a>> {
+    let name = parser.expect_kind(Kind::Ident)?.text;
+    let generics = parser.parse_generics()?;
+
+    let start = parser.position();
+    let (fields, where_clause) = if parser.is("(") {
+        let fields = parse_fields(parser)?;
+        (fields, parser.parse_where_clause()?)
+    } else {
+        let where_clause = parser.parse_where_clause()?;
+        let fields = if parser.is("{") {
+            parse_fields(parser)?
+        } else {
+            Fields::Unit
+        };
+        (fields, where_clause)
+    };
+
+    Some(StructDecl {
+        visibility,
+        name,
+        generics,
+        fields,
+        fields_omitted: parser.has_comment_since(start),
+        where_clause,
+    })
+}
+
+fn parse_fields<'a>(parser: &mut Parser<'a>) -> Option<Fields<'a>> {
+    let named = if parser.eat("{") {
+        true
+    } else if parser.eat("(") {
+        false
+    } else {
+        return Some(Fields::Unit);
+    };
+    let close = if named { "}" } else { ")" };
+
+    let mut fields = vec![];
+    while !parser.eat(close) {
+        parser.skip_attributes();
+        let visibility = parser.parse_visibility();
+        let name = if named {
+            let name = parser.expect_kind(Kind::Ident)?.text;
+            parser.expect(":")?;
+            Some(name)
+        } else {
+            None
+        };
+        let ty = parser.parse_type()?;
+        fields.push(Field {
+            visibility,
+            name,
+            ty,
+        });
+        if !parser.eat(",") {
+            parser.expect(close)?;
+            break;
+        }
+    }
+
+    Some(if named {
+        Fields::Named(fields)
+    } else {
+        Fields::Unnamed(fields)
+    })
+}
+
+fn parse_enum<'a>(
+    parser: &mut Parser<'a>,
+    visibility: Option<Cow<'a, str>>,
+) -> Option<EnumDecl<'a>> {
+    let name = parser.expect_kind(Kind::Ident)?.text;
+    let generics = parser.parse_generics()?;
+    let where_clause = parser.parse_where_clause()?;
+
+    parser.expect("{")?;
+    let mut variants = vec![];
+    while !parser.eat("}") {
+        parser.skip_attributes();
+        let name = parser.expect_kind(Kind::Ident)?.text;
+        let fields = parse_fields(parser)?;
+        let discriminant = if parser.eat("=") {
+            Some(parser.parse_raw_until(&[",", "}"]))
+        } else {
+            None
+        };
+        variants.push(Variant {
+            name,
+            fields,
+            discriminant,
+        });
+        if !parser.eat(",") {
+            parser.expect("}")?;
+            break;
+        }
+    }
+
+    Some(EnumDecl {
+        visibility,
+        name,
+        generics,
+        where_clause,
+        variants,
+    })
+}
+
+fn parse_trait<'a>(
+    parser: &mut Parser<'a>,
+    visibility: Option<Cow<'a, str>>,
+) -> Option<TraitDecl<'a>> {
+    let unsafe_ = parser.eat("unsafe");
+    let auto = parser.eat("auto");
+    parser.expect("trait")?;
+    let name = parser.expect_kind(Kind::Ident)?.text;
+    let generics = parser.parse_generics()?;
+    let supertraits = if parser.eat(":") {
+        parser.parse_bounds()?
+    } else {
+        vec![]
+    };
+    let where_clause = parser.parse_where_clause()?;
+
+    let mut items = vec![];
+    if parser.eat("{") {
+        while !parser.eat("}") {
+            let mut item = parse_item(parser)?;
+            match &mut item {
+                TraitItem::Fn { provided, .. } if parser.is("{") => {
+                    parser.skip_group();
+                    *provided = true;
+                }
+                _ => {
+                    parser.expect(";")?;
+                }
+            }
+            items.push(item);
+        }
+    }
+
+    Some(TraitDecl {
+        visibility,
+        unsafe_,
+        auto,
+        name,
+        generics,
+        supertraits,
+        where_clause,
+        items,
+    })
+}
+
+pub fn parse_trait_item<'a>(header: &[TextPart<'a>]) -> Option<TraitItem<'a>> {
+    let mut parser = Parser::new(tokenize(header));
+    let item = parse_item(&mut parser)?;
+    parser.eat(";");
+    parser.is_end().then_some(item)
+}
+
+fn parse_item<'a>(parser: &mut Parser<'a>) -> Option<TraitItem<'a>> {
+    parser.skip_attributes();
+
+    if parser.eat("type") {
+        let name = parser.expect_kind(Kind::Ident)?.text;
+        let generics = parser.parse_generics()?;
+        let bounds = if parser.eat(":") {
+            parser.parse_bounds()?
+        } else {
+            vec![]
+        };
+        let where_clause = parser.parse_where_clause()?;
+        let default = if parser.eat("=") {
+            Some(parser.parse_type()?)
+        } else {
+            None
+        };
+        return Some(TraitItem::Type {
+            name,
+            generics,
+            bounds,
+            where_clause,
+            default,
+        });
+    }
+
+    if parser.is("const") && parser.peek_nth(2).is_some_and(|token| token.text == ":") {
+        parser.next();
+        let name = parser.expect_kind(Kind::Ident)?.text;
+        parser.expect(":")?;
+        let ty = parser.parse_type()?;
+        let default = if parser.eat("=") {
+            Some(parser.parse_raw_until(&[";"]))
+        } else {
+            None
+        };
+        return Some(TraitItem::Const { name, ty, default });
+    }
+
+    let signature = parse_fn(parser)?;
+    Some(TraitItem::Fn {
+        signature,
+        provided: false,
+    })
+}
+
+fn parse_type_alias<'a>(
+    parser: &mut Parser<'a>,
+    visibility: Option<Cow<'a, str>>,
+) -> Option<TypeAliasDecl<'a>> {
+    let name = parser.expect_kind(Kind::Ident)?.text;
+    let generics = parser.parse_generics()?;
+    let where_clause = parser.parse_where_clause()?;
+    parser.expect("=")?;
+    let ty = parser.parse_type()?;
+
+    Some(TypeAliasDecl {
+        visibility,
+        name,
+        generics,
+        where_clause,
+        ty,
+    })
+}
+
+fn write_visibility(f: &mut fmt::Formatter<'_>, visibility: &Option<Cow<str>>) -> fmt::Result {
+    if let Some(visibility) = visibility {
+        write!(f, "{} ", visibility)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Declaration<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Declaration::Struct(decl) => decl.write(f, "struct"),
+            Declaration::Union(decl) => decl.write(f, "union"),
+            Declaration::Enum(decl) => write!(f, "{}", decl),
+            Declaration::Trait(decl) => write!(f, "{}", decl),
+            Declaration::TypeAlias(decl) => write!(f, "{}", decl),
+            Declaration::Function(signature) => write!(f, "{}", signature),
+        }
+    }
+}
+
+impl StructDecl<'_> {
+    fn write(&self, f: &mut fmt::Formatter<'_>, keyword: &str) -> fmt::Result {
+        write_visibility(f, &self.visibility)?;
+        write!(f, "{} {}", keyword, self.name)?;
+        write_generics(f, &self.generics)?;
+        match &self.fields {
+            Fields::Unnamed(fields) if self.fields_omitted => {
+                f.write_str("(")?;
+                write_omitted_fields(f, fields)?;
+                f.write_str(")")?;
+                write_where_clause(f, &self.where_clause)?;
+                f.write_str(";")
+            }
+            Fields::Unnamed(_) => {
+                write!(f, "{}", self.fields)?;
+                write_where_clause(f, &self.where_clause)?;
+                f.write_str(";")
+            }
+            Fields::Named(fields) if self.fields_omitted => {
+                write_where_clause(f, &self.where_clause)?;
+                f.write_str(" { ")?;
+                write_omitted_fields(f, fields)?;
+                f.write_str(" }")
+            }
+            Fields::Named(_) => {
+                write_where_clause(f, &self.where_clause)?;
+                write!(f, " {}", self.fields)
+            }
+            Fields::Unit => {
+                write_where_clause(f, &self.where_clause)?;
+                f.write_str(";")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Fields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fields::Named(fields) if fields.is_empty() => f.write_str("{ }"),
+            Fields::Named(fields) => {
+                f.write_str("{ ")?;
+                write_separated(f, fields, ", ")?;
+                f.write_str(" }")
+            }
+            Fields::Unnamed(fields) => {
+                f.write_str("(")?;
+                write_separated(f, fields, ", ")?;
+                f.write_str(")")
+            }
+            Fields::Unit => Ok(()),
+        }
+    }
+}
+
+fn write_omitted_fields(f: &mut fmt::Formatter<'_>, fields: &[Field<'_>]) -> fmt::Result {
+    for field in fields {
+        write!(f, "{}, ", field)?;
+    }
+    f.write_str("/* fields omitted */")
+}
+
+impl fmt::Display for Field<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_visibility(f, &self.visibility)?;
+        if let Some(name) = &self.name {
+            write!(f, "{}: ", name)?;
+        }
+        write!(f, "{}", self.ty)
+    }
+}
+
+impl fmt::Display for EnumDecl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_visibility(f, &self.visibility)?;
+        write!(f, "enum {}", self.name)?;
+        write_generics(f, &self.generics)?;
+        write_where_clause(f, &self.where_clause)?;
+        f.write_str(" { ")?;
+        write_separated(f, &self.variants, ", ")?;
+        f.write_str(" }")
+    }
+}
+
+impl fmt::Display for Variant<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        if let Fields::Named(_) = self.fields {
+            f.write_str(" ")?;
+        }
+        write!(f, "{}", self.fields)?;
+        if let Some(discriminant) = &self.discriminant {
+            write!(f, " = {}", discriminant)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TraitDecl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_visibility(f, &self.visibility)?;
+        if self.unsafe_ {
+            f.write_str("unsafe ")?;
+        }
+        if self.auto {
+            f.write_str("auto ")?;
+        }
+        write!(f, "trait {}", self.name)?;
+        write_generics(f, &self.generics)?;
+        if !self.supertraits.is_empty() {
+            f.write_str(": ")?;
+            write_separated(f, &self.supertraits, " + ")?;
+        }
+        write_where_clause(f, &self.where_clause)?;
+        f.write_str(" {")?;
+        for item in &self.items {
+            write!(f, " {}", item)?;
+        }
+        f.write_str(" }")
+    }
+}
+
+impl fmt::Display for TraitItem<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraitItem::Type {
+                name,
+                generics,
+                bounds,
+                where_clause,
+                default,
+            } => {
+                write!(f, "type {}", name)?;
+                write_generics(f, generics)?;
+                if !bounds.is_empty() {
+                    f.write_str(": ")?;
+                    write_separated(f, bounds, " + ")?;
+                }
+                write_where_clause(f, where_clause)?;
+                if let Some(default) = default {
+                    write!(f, " = {}", default)?;
+                }
+                f.write_str(";")
+            }
+            TraitItem::Const { name, ty, default } => {
+                write!(f, "const {}: {}", name, ty)?;
+                if let Some(default) = default {
+                    write!(f, " = {}", default)?;
+                }
+                f.write_str(";")
+            }
+            TraitItem::Fn {
+                signature,
+                provided,
+            } => {
+                write!(f, "{}", signature)?;
+                f.write_str(if *provided { " { ... }" } else { ";" })
+            }
+        }
+    }
+}
+
+impl fmt::Display for TypeAliasDecl<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_visibility(f, &self.visibility)?;
+        write!(f, "type {}", self.name)?;
+        write_generics(f, &self.generics)?;
+        write_where_clause(f, &self.where_clause)?;
+        write!(f, " = {};", self.ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::atom::TextPart;
+
+    use super::{parse_declaration, parse_trait_item, Declaration, Fields, TraitItem};
+
+    fn parse(source: &str) -> Declaration<'_> {
+        parse_declaration(&[TextPart::Text(Cow::Borrowed(source))]).unwrap()
+    }
+
+    #[test]
+    fn named_fields() {
+        let declaration = parse("pub struct Point<T> where T: Copy { pub x: T, y: T }");
+        match &declaration {
+            Declaration::Struct(decl) => {
+                assert_eq!(decl.name, "Point");
+                assert!(!decl.fields_omitted);
+                match &decl.fields {
+                    Fields::Named(fields) => {
+                        let names: Vec<_> =
+                            fields.iter().map(|field| field.name.as_deref()).collect();
+                        assert_eq!(names, [Some("x"), Some("y")]);
+                        assert_eq!(fields[0].visibility.as_deref(), Some("pub"));
+                        assert_eq!(fields[1].visibility, None);
+                    }
+                    fields => panic!("unexpected fields {:?}", fields),
+                }
+            }
+            declaration => panic!("unexpected declaration {:?}", declaration),
+        }
+        assert_eq!(
+            declaration.to_string(),
+            "pub struct Point<T> where T: Copy { pub x: T, y: T }"
+        );
+    }
+
+    #[test]
+    fn omitted_fields() {
+        let declaration =
+            parse("pub struct DynMetadata<Dyn> where Dyn: ?Sized, { /* fields omitted */ }");
+        match &declaration {
+            Declaration::Struct(decl) => {
+                assert_eq!(decl.fields, Fields::Named(vec![]));
+                assert!(decl.fields_omitted);
+            }
+            declaration => panic!("unexpected declaration {:?}", declaration),
+        }
+        assert_eq!(
+            declaration.to_string(),
+            "pub struct DynMetadata<Dyn> where Dyn: ?Sized { /* fields omitted */ }"
+        );
+
+        let declaration =
+            parse("pub struct Html { pub errors: Vec<Cow<'static, str>>, /* private fields */ }");
+        assert_eq!(
+            declaration.to_string(),
+            "pub struct Html { pub errors: Vec<Cow<'static, str>>, /* fields omitted */ }"
+        );
+
+        let declaration = parse("pub struct Id(/* private fields */);");
+        match &declaration {
+            Declaration::Struct(decl) => {
+                assert_eq!(decl.fields, Fields::Unnamed(vec![]));
+                assert!(decl.fields_omitted);
+            }
+            declaration => panic!("unexpected declaration {:?}", declaration),
+        }
+        assert_eq!(
+            declaration.to_string(),
+            "pub struct Id(/* fields omitted */);"
+        );
+    }
+
+    #[test]
+    fn unit_and_tuple_structs() {
+        assert_eq!(
+            parse("pub struct Marker;").to_string(),
+            "pub struct Marker;"
+        );
+        assert_eq!(
+            parse("pub struct Wrapper<'a>(pub &'a str, u8);").to_string(),
+            "pub struct Wrapper<'a>(pub &'a str, u8);"
+        );
+    }
+
+    #[test]
+    fn enums() {
+        let declaration = parse("pub enum Option<T> { None, Some(T), }");
+        match &declaration {
+            Declaration::Enum(decl) => {
+                let names: Vec<_> = decl.variants.iter().map(|variant| &*variant.name).collect();
+                assert_eq!(names, ["None", "Some"]);
+                assert_eq!(decl.variants[0].fields, Fields::Unit);
+                assert!(
+                    matches!(&decl.variants[1].fields, Fields::Unnamed(fields) if fields.len() == 1)
+                );
+            }
+            declaration => panic!("unexpected declaration {:?}", declaration),
+        }
+        assert_eq!(
+            declaration.to_string(),
+            "pub enum Option<T> { None, Some(T) }"
+        );
+    }
+
+    #[test]
+    fn type_aliases_and_functions() {
+        assert!(matches!(
+            parse("pub type Result<T> = Result<T, Error>;"),
+            Declaration::TypeAlias(decl) if decl.name == "Result"
+        ));
+        assert!(matches!(
+            parse("pub fn pipe() -> Result<(PipeReader, PipeWriter)>"),
+            Declaration::Function(signature) if signature.name == "pipe"
+        ));
+    }
+
+    #[test]
+    fn trait_items() {
+        fn item(source: &str) -> Option<TraitItem<'_>> {
+            parse_trait_item(&[TextPart::Text(Cow::Borrowed(source))])
+        }
+
+        assert!(matches!(
+            item("type GuardMarker;"),
+            Some(TraitItem::Type { name, bounds, default: None, .. }) if name == "GuardMarker" && bounds.is_empty()
+        ));
+        assert!(matches!(
+            item("const INIT: Self;"),
+            Some(TraitItem::Const { name, default: None, .. }) if name == "INIT"
+        ));
+        assert!(matches!(
+            item("fn lock(&self)"),
+            Some(TraitItem::Fn { signature, provided: false }) if signature.name == "lock"
+        ));
+        assert!(item("fn lock(&self) extra").is_none());
+    }
+
+    #[test]
+    fn traits() {
+        let declaration = parse(
+            "pub unsafe trait RawMutex { const INIT: Self; type GuardMarker; \
+             // Required methods
+             fn lock(&self); \
+             // Provided method
+             fn is_locked(&self) -> bool { ... } }",
+        );
+        match &declaration {
+            Declaration::Trait(decl) => {
+                assert!(decl.unsafe_);
+                assert_eq!(decl.name, "RawMutex");
+                let provided: Vec<_> = decl
+                    .items
+                    .iter()
+                    .filter_map(|item| match item {
+                        TraitItem::Fn {
+                            signature,
+                            provided,
+                        } => Some((&*signature.name, *provided)),
+                        _ => None,
+                    })
+                    .collect();
+                assert_eq!(provided, [("lock", false), ("is_locked", true)]);
+                assert_eq!(decl.items.len(), 4);
+            }
+            declaration => panic!("unexpected declaration {:?}", declaration),
+        }
+    }
+}
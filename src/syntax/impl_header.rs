@@ -0,0 +1,117 @@
+use crate::atom::TextPart;
+
+use super::{
+    into_owned_generics, into_owned_where_clause, lexer::tokenize, parser::Parser, GenericParam,
+    Type, TypePath, WherePredicate,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ImplHeader<'a> {
+    pub generics: Vec<GenericParam<'a>>,
+    /// `None` for inherent impls.
+    pub trait_path: Option<TypePath<'a>>,
+    /// Set for `impl !Send for T`.
+    pub negative: bool,
+    pub unsafe_: bool,
+    pub self_ty: Type<'a>,
+    pub where_clause: Vec<WherePredicate<'a>>,
+}
+
+impl ImplHeader<'_> {
+    pub fn into_owned(self) -> ImplHeader<'static> {
+        ImplHeader {
+            generics: into_owned_generics(self.generics),
+            trait_path: self.trait_path.map(TypePath::into_owned),
+            negative: self.negative,
+            unsafe_: self.unsafe_,
+            self_ty: self.self_ty.into_owned(),
+            where_clause: into_owned_where_clause(self.where_clause),
+        }
+    }
+}
+
+pub fn parse_impl_header<'a>(header: &[TextPart<'a>]) -> Option<ImplHeader<'a>> {
+    let mut parser = Parser::new(tokenize(header));
+
+    parser.eat("default");
+    let unsafe_ = parser.eat("unsafe");
+    parser.expect("impl")?;
+    let generics = parser.parse_generics()?;
+    parser.eat("const");
+
+    let negative = parser.eat("!");
+    let ty = parser.parse_type()?;
+    let (trait_path, self_ty) = if parser.eat("for") {
+        match ty {
+            Type::Path(path) => (Some(path), parser.parse_type()?),
+            _ => return None,
+        }
+    } else if negative {
+        return None;
+    } else {
+        (None, ty)
+    };
+    let where_clause = parser.parse_where_clause()?;
+
+    parser.is_end().then_some(ImplHeader {
+        generics,
+        trait_path,
+        negative,
+        unsafe_,
+        self_ty,
+        where_clause,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::atom::TextPart;
+
+    use super::{parse_impl_header, ImplHeader};
+
+    fn parse(source: &str) -> Option<ImplHeader<'_>> {
+        parse_impl_header(&[TextPart::Text(Cow::Borrowed(source))])
+    }
+
+    #[test]
+    fn inherent_impls() {
+        let header = parse("impl<T> Option<T>").unwrap();
+        assert_eq!(header.generics.len(), 1);
+        assert!(header.trait_path.is_none());
+        assert_eq!(header.self_ty.to_string(), "Option<T>");
+    }
+
+    #[test]
+    fn trait_impls() {
+        let header =
+            parse("impl<'de, 'a, A> SeqAccess<'de> for &'a mut A where A: ?Sized + SeqAccess<'de>")
+                .unwrap();
+        assert_eq!(header.generics.len(), 3);
+        assert_eq!(header.trait_path.unwrap().to_string(), "SeqAccess<'de>");
+        assert_eq!(header.self_ty.to_string(), "&'a mut A");
+        assert_eq!(header.where_clause.len(), 1);
+        assert!(!header.negative);
+        assert!(!header.unsafe_);
+    }
+
+    #[test]
+    fn negative_and_unsafe_impls() {
+        let header = parse("impl<Dyn: ?Sized> !Sync for DynMetadata<Dyn>").unwrap();
+        assert!(header.negative);
+        assert_eq!(header.trait_path.unwrap().to_string(), "Sync");
+
+        let header = parse("unsafe impl<T: Send> Send for Box<T>").unwrap();
+        assert!(header.unsafe_);
+        assert!(!header.negative);
+    }
+
+    #[test]
+    fn rejects_malformed_headers() {
+        assert!(parse("impl !Sync").is_none());
+        assert!(parse("impl<T> [T] for Vec<T>").is_none());
+        assert!(parse("fn new() -> Self").is_none());
+    }
+}
@@ -0,0 +1,120 @@
+use std::borrow::Cow;
+
+use crate::atom::{TextPart, TextStyle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Ident,
+    Lifetime,
+    Literal,
+    Punct,
+    Comment,
+}
+
+#[derive(Debug, Clone)]
+pub struct Token<'a> {
+    pub kind: Kind,
+    pub text: Cow<'a, str>,
+    pub href: Option<Cow<'a, str>>,
+}
+
+const PUNCTS: &[&str] = &["...", "..", "->", "=>", "::"];
+
+pub fn tokenize<'a>(parts: &[TextPart<'a>]) -> Vec<Token<'a>> {
+    let mut tokens = vec![];
+    let mut links: Vec<Option<&Cow<'a, str>>> = vec![];
+
+    for part in parts {
+        match part {
+            TextPart::Text(text) | TextPart::Token(_, text) => {
+                let href = links.iter().rev().find_map(|link| *link);
+                lex(text, |kind, start, end| {
+                    tokens.push(Token {
+                        kind,
+                        text: slice(text, start, end),
+                        href: href.filter(|_| kind == Kind::Ident).cloned(),
+                    })
+                });
+            }
+            TextPart::BeginStyle(TextStyle::Link(href)) => links.push(Some(href)),
+            TextPart::BeginStyle(_) => links.push(None),
+            TextPart::EndStyle => {
+                links.pop();
+            }
+            TextPart::Image(_) | TextPart::Table | TextPart::FootnoteReference(_) => {}
+        }
+    }
+
+    tokens
+}
+
+fn lex(source: &str, mut emit: impl FnMut(Kind, usize, usize)) {
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let kind = if c.is_whitespace() {
+            continue;
+        } else if source[start..].starts_with("//") {
+            while chars.next_if(|(_, c)| *c != '\n').is_some() {}
+            continue;
+        } else if source[start..].starts_with("/*") {
+            let end = source[start + 2..]
+                .find("*/")
+                .map_or(source.len(), |end| start + 2 + end + 2);
+            while chars.next_if(|(index, _)| *index < end).is_some() {}
+            Kind::Comment
+        } else if c == '_' || c.is_alphabetic() {
+            while chars
+                .next_if(|(_, c)| *c == '_' || c.is_alphanumeric())
+                .is_some()
+            {}
+            Kind::Ident
+        } else if c.is_ascii_digit() {
+            while chars
+                .next_if(|(_, c)| *c == '_' || *c == '.' || c.is_alphanumeric())
+                .is_some()
+            {}
+            Kind::Literal
+        } else if c == '\'' {
+            while chars
+                .next_if(|(_, c)| *c == '_' || c.is_alphanumeric())
+                .is_some()
+            {}
+            if chars.next_if(|(_, c)| *c == '\'').is_some() {
+                Kind::Literal
+            } else {
+                Kind::Lifetime
+            }
+        } else if c == '"' {
+            let mut escaped = false;
+            for (_, c) in chars.by_ref() {
+                match c {
+                    '\\' if !escaped => escaped = true,
+                    '"' if !escaped => break,
+                    _ => escaped = false,
+                }
+            }
+            Kind::Literal
+        } else {
+            if let Some(punct) = PUNCTS
+                .iter()
+                .find(|punct| source[start..].starts_with(**punct))
+            {
+                for _ in 1..punct.len() {
+                    chars.next();
+                }
+            }
+            Kind::Punct
+        };
+
+        let end = chars.peek().map_or(source.len(), |(end, _)| *end);
+        emit(kind, start, end);
+    }
+}
+
+fn slice<'a>(text: &Cow<'a, str>, start: usize, end: usize) -> Cow<'a, str> {
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(&text[start..end]),
+        Cow::Owned(text) => Cow::Owned(text[start..end].to_string()),
+    }
+}
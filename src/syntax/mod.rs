@@ -0,0 +1,686 @@
+use std::{borrow::Cow, fmt};
+
+use crate::atom::into_owned;
+
+pub mod declaration;
+pub mod impl_header;
+mod lexer;
+mod parser;
+pub mod signature;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Type<'a> {
+    Path(TypePath<'a>),
+    QualifiedPath {
+        self_ty: Box<Type<'a>>,
+        trait_path: Option<TypePath<'a>>,
+        segments: Vec<PathSegment<'a>>,
+    },
+    Reference {
+        lifetime: Option<Cow<'a, str>>,
+        mutable: bool,
+        ty: Box<Type<'a>>,
+    },
+    Pointer {
+        mutable: bool,
+        ty: Box<Type<'a>>,
+    },
+    Slice(Box<Type<'a>>),
+    Array {
+        ty: Box<Type<'a>>,
+        len: Cow<'a, str>,
+    },
+    Tuple(Vec<Type<'a>>),
+    FnPointer(Box<FnPointer<'a>>),
+    ImplTrait(Vec<Bound<'a>>),
+    DynTrait(Vec<Bound<'a>>),
+    Never,
+    Infer,
+}
+
+impl Type<'_> {
+    pub fn into_owned(self) -> Type<'static> {
+        match self {
+            Type::Path(path) => Type::Path(path.into_owned()),
+            Type::QualifiedPath {
+                self_ty,
+                trait_path,
+                segments,
+            } => Type::QualifiedPath {
+                self_ty: Box::new(self_ty.into_owned()),
+                trait_path: trait_path.map(TypePath::into_owned),
+                segments: segments.into_iter().map(PathSegment::into_owned).collect(),
+            },
+            Type::Reference {
+                lifetime,
+                mutable,
+                ty,
+            } => Type::Reference {
+                lifetime: lifetime.map(into_owned),
+                mutable,
+                ty: Box::new(ty.into_owned()),
+            },
+            Type::Pointer { mutable, ty } => Type::Pointer {
+                mutable,
+                ty: Box::new(ty.into_owned()),
+            },
+            Type::Slice(ty) => Type::Slice(Box::new(ty.into_owned())),
+            Type::Array { ty, len } => Type::Array {
+                ty: Box::new(ty.into_owned()),
+                len: into_owned(len),
+            },
+            Type::Tuple(types) => Type::Tuple(types.into_iter().map(Type::into_owned).collect()),
+            Type::FnPointer(pointer) => Type::FnPointer(Box::new(pointer.into_owned())),
+            Type::ImplTrait(bounds) => Type::ImplTrait(into_owned_bounds(bounds)),
+            Type::DynTrait(bounds) => Type::DynTrait(into_owned_bounds(bounds)),
+            Type::Never => Type::Never,
+            Type::Infer => Type::Infer,
+        }
+    }
+
+    /// Every path in the type, each keeping the `href` rustdoc linked it to.
+    pub fn paths(&self) -> Vec<&TypePath<'_>> {
+        let mut paths = vec![];
+        self.collect_paths(&mut paths);
+        paths
+    }
+
+    fn collect_paths<'s>(&'s self, paths: &mut Vec<&'s TypePath<'s>>) {
+        match self {
+            Type::Path(path) => path.collect_paths(paths),
+            Type::QualifiedPath {
+                self_ty,
+                trait_path,
+                segments,
+            } => {
+                self_ty.collect_paths(paths);
+                if let Some(trait_path) = trait_path {
+                    trait_path.collect_paths(paths);
+                }
+                for segment in segments {
+                    segment.collect_paths(paths);
+                }
+            }
+            Type::Reference { ty, .. }
+            | Type::Pointer { ty, .. }
+            | Type::Slice(ty)
+            | Type::Array { ty, .. } => ty.collect_paths(paths),
+            Type::Tuple(types) => types.iter().for_each(|ty| ty.collect_paths(paths)),
+            Type::FnPointer(pointer) => {
+                pointer.inputs.iter().for_each(|ty| ty.collect_paths(paths));
+                if let Some(output) = &pointer.output {
+                    output.collect_paths(paths);
+                }
+            }
+            Type::ImplTrait(bounds) | Type::DynTrait(bounds) => {
+                bounds.iter().for_each(|bound| bound.collect_paths(paths))
+            }
+            Type::Never | Type::Infer => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TypePath<'a> {
+    pub global: bool,
+    pub segments: Vec<PathSegment<'a>>,
+}
+
+impl TypePath<'_> {
+    pub fn into_owned(self) -> TypePath<'static> {
+        TypePath {
+            global: self.global,
+            segments: self
+                .segments
+                .into_iter()
+                .map(PathSegment::into_owned)
+                .collect(),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.segments.last().map_or("", |segment| &segment.name)
+    }
+
+    pub fn href(&self) -> Option<&str> {
+        self.segments
+            .iter()
+            .rev()
+            .find_map(|segment| segment.href.as_deref())
+    }
+
+    fn collect_paths<'s>(&'s self, paths: &mut Vec<&'s TypePath<'s>>) {
+        paths.push(self);
+        for segment in &self.segments {
+            segment.collect_paths(paths);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PathSegment<'a> {
+    pub name: Cow<'a, str>,
+    pub href: Option<Cow<'a, str>>,
+    pub args: Option<GenericArgs<'a>>,
+}
+
+impl PathSegment<'_> {
+    pub fn into_owned(self) -> PathSegment<'static> {
+        PathSegment {
+            name: into_owned(self.name),
+            href: self.href.map(into_owned),
+            args: self.args.map(GenericArgs::into_owned),
+        }
+    }
+
+    fn collect_paths<'s>(&'s self, paths: &mut Vec<&'s TypePath<'s>>) {
+        match &self.args {
+            Some(GenericArgs::AngleBracketed(args)) => {
+                for arg in args {
+                    match arg {
+                        GenericArg::Type(ty) | GenericArg::Binding { ty, .. } => {
+                            ty.collect_paths(paths)
+                        }
+                        GenericArg::Constraint { bounds, .. } => {
+                            bounds.iter().for_each(|bound| bound.collect_paths(paths))
+                        }
+                        GenericArg::Lifetime(_) | GenericArg::Const(_) => {}
+                    }
+                }
+            }
+            Some(GenericArgs::Parenthesized { inputs, output }) => {
+                inputs.iter().for_each(|ty| ty.collect_paths(paths));
+                if let Some(output) = output {
+                    output.collect_paths(paths);
+                }
+            }
+            None => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum GenericArgs<'a> {
+    AngleBracketed(Vec<GenericArg<'a>>),
+    Parenthesized {
+        inputs: Vec<Type<'a>>,
+        output: Option<Box<Type<'a>>>,
+    },
+}
+
+impl GenericArgs<'_> {
+    pub fn into_owned(self) -> GenericArgs<'static> {
+        match self {
+            GenericArgs::AngleBracketed(args) => {
+                GenericArgs::AngleBracketed(args.into_iter().map(GenericArg::into_owned).collect())
+            }
+            GenericArgs::Parenthesized { inputs, output } => GenericArgs::Parenthesized {
+                inputs: inputs.into_iter().map(Type::into_owned).collect(),
+                output: output.map(|output| Box::new(output.into_owned())),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum GenericArg<'a> {
+    Lifetime(Cow<'a, str>),
+    Type(Type<'a>),
+    Const(Cow<'a, str>),
+    Binding {
+        name: Cow<'a, str>,
+        ty: Type<'a>,
+    },
+    Constraint {
+        name: Cow<'a, str>,
+        bounds: Vec<Bound<'a>>,
+    },
+}
+
+impl GenericArg<'_> {
+    pub fn into_owned(self) -> GenericArg<'static> {
+        match self {
+            GenericArg::Lifetime(lifetime) => GenericArg::Lifetime(into_owned(lifetime)),
+            GenericArg::Type(ty) => GenericArg::Type(ty.into_owned()),
+            GenericArg::Const(value) => GenericArg::Const(into_owned(value)),
+            GenericArg::Binding { name, ty } => GenericArg::Binding {
+                name: into_owned(name),
+                ty: ty.into_owned(),
+            },
+            GenericArg::Constraint { name, bounds } => GenericArg::Constraint {
+                name: into_owned(name),
+                bounds: into_owned_bounds(bounds),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Bound<'a> {
+    Trait {
+        for_lifetimes: Vec<Cow<'a, str>>,
+        maybe: bool,
+        path: TypePath<'a>,
+    },
+    Lifetime(Cow<'a, str>),
+}
+
+impl Bound<'_> {
+    pub fn into_owned(self) -> Bound<'static> {
+        match self {
+            Bound::Trait {
+                for_lifetimes,
+                maybe,
+                path,
+            } => Bound::Trait {
+                for_lifetimes: for_lifetimes.into_iter().map(into_owned).collect(),
+                maybe,
+                path: path.into_owned(),
+            },
+            Bound::Lifetime(lifetime) => Bound::Lifetime(into_owned(lifetime)),
+        }
+    }
+
+    fn collect_paths<'s>(&'s self, paths: &mut Vec<&'s TypePath<'s>>) {
+        if let Bound::Trait { path, .. } = self {
+            path.collect_paths(paths);
+        }
+    }
+}
+
+pub(crate) fn into_owned_bounds(bounds: Vec<Bound<'_>>) -> Vec<Bound<'static>> {
+    bounds.into_iter().map(Bound::into_owned).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FnPointer<'a> {
+    pub for_lifetimes: Vec<Cow<'a, str>>,
+    pub unsafe_: bool,
+    pub abi: Option<Cow<'a, str>>,
+    pub inputs: Vec<Type<'a>>,
+    pub output: Option<Type<'a>>,
+}
+
+impl FnPointer<'_> {
+    pub fn into_owned(self) -> FnPointer<'static> {
+        FnPointer {
+            for_lifetimes: self.for_lifetimes.into_iter().map(into_owned).collect(),
+            unsafe_: self.unsafe_,
+            abi: self.abi.map(into_owned),
+            inputs: self.inputs.into_iter().map(Type::into_owned).collect(),
+            output: self.output.map(Type::into_owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum GenericParam<'a> {
+    Lifetime {
+        name: Cow<'a, str>,
+        bounds: Vec<Cow<'a, str>>,
+    },
+    Type {
+        name: Cow<'a, str>,
+        bounds: Vec<Bound<'a>>,
+        default: Option<Type<'a>>,
+    },
+    Const {
+        name: Cow<'a, str>,
+        ty: Type<'a>,
+        default: Option<Cow<'a, str>>,
+    },
+}
+
+impl GenericParam<'_> {
+    pub fn into_owned(self) -> GenericParam<'static> {
+        match self {
+            GenericParam::Lifetime { name, bounds } => GenericParam::Lifetime {
+                name: into_owned(name),
+                bounds: bounds.into_iter().map(into_owned).collect(),
+            },
+            GenericParam::Type {
+                name,
+                bounds,
+                default,
+            } => GenericParam::Type {
+                name: into_owned(name),
+                bounds: into_owned_bounds(bounds),
+                default: default.map(Type::into_owned),
+            },
+            GenericParam::Const { name, ty, default } => GenericParam::Const {
+                name: into_owned(name),
+                ty: ty.into_owned(),
+                default: default.map(into_owned),
+            },
+        }
+    }
+}
+
+pub(crate) fn into_owned_generics(generics: Vec<GenericParam<'_>>) -> Vec<GenericParam<'static>> {
+    generics.into_iter().map(GenericParam::into_owned).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum WherePredicate<'a> {
+    Bound {
+        for_lifetimes: Vec<Cow<'a, str>>,
+        ty: Type<'a>,
+        bounds: Vec<Bound<'a>>,
+    },
+    Lifetime {
+        lifetime: Cow<'a, str>,
+        bounds: Vec<Cow<'a, str>>,
+    },
+}
+
+impl WherePredicate<'_> {
+    pub fn into_owned(self) -> WherePredicate<'static> {
+        match self {
+            WherePredicate::Bound {
+                for_lifetimes,
+                ty,
+                bounds,
+            } => WherePredicate::Bound {
+                for_lifetimes: for_lifetimes.into_iter().map(into_owned).collect(),
+                ty: ty.into_owned(),
+                bounds: into_owned_bounds(bounds),
+            },
+            WherePredicate::Lifetime { lifetime, bounds } => WherePredicate::Lifetime {
+                lifetime: into_owned(lifetime),
+                bounds: bounds.into_iter().map(into_owned).collect(),
+            },
+        }
+    }
+}
+
+pub(crate) fn into_owned_where_clause(
+    predicates: Vec<WherePredicate<'_>>,
+) -> Vec<WherePredicate<'static>> {
+    predicates
+        .into_iter()
+        .map(WherePredicate::into_owned)
+        .collect()
+}
+
+fn write_separated<T: fmt::Display>(
+    f: &mut fmt::Formatter<'_>,
+    items: &[T],
+    separator: &str,
+) -> fmt::Result {
+    for (index, item) in items.iter().enumerate() {
+        if index > 0 {
+            f.write_str(separator)?;
+        }
+        write!(f, "{}", item)?;
+    }
+    Ok(())
+}
+
+fn write_for_lifetimes(f: &mut fmt::Formatter<'_>, lifetimes: &[Cow<str>]) -> fmt::Result {
+    if !lifetimes.is_empty() {
+        f.write_str("for<")?;
+        write_separated(f, lifetimes, ", ")?;
+        f.write_str("> ")?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for Type<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Path(path) => write!(f, "{}", path),
+            Type::QualifiedPath {
+                self_ty,
+                trait_path,
+                segments,
+            } => {
+                write!(f, "<{}", self_ty)?;
+                if let Some(trait_path) = trait_path {
+                    write!(f, " as {}", trait_path)?;
+                }
+                f.write_str(">")?;
+                for segment in segments {
+                    write!(f, "::{}", segment)?;
+                }
+                Ok(())
+            }
+            Type::Reference {
+                lifetime,
+                mutable,
+                ty,
+            } => {
+                f.write_str("&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                write!(f, "{}", ty)
+            }
+            Type::Pointer { mutable, ty } => {
+                let mutability = if *mutable { "mut" } else { "const" };
+                write!(f, "*{} {}", mutability, ty)
+            }
+            Type::Slice(ty) => write!(f, "[{}]", ty),
+            Type::Array { ty, len } => write!(f, "[{}; {}]", ty, len),
+            Type::Tuple(types) => {
+                f.write_str("(")?;
+                write_separated(f, types, ", ")?;
+                if types.len() == 1 {
+                    f.write_str(",")?;
+                }
+                f.write_str(")")
+            }
+            Type::FnPointer(pointer) => write!(f, "{}", pointer),
+            Type::ImplTrait(bounds) => {
+                f.write_str("impl ")?;
+                write_separated(f, bounds, " + ")
+            }
+            Type::DynTrait(bounds) => {
+                f.write_str("dyn ")?;
+                write_separated(f, bounds, " + ")
+            }
+            Type::Never => f.write_str("!"),
+            Type::Infer => f.write_str("_"),
+        }
+    }
+}
+
+impl fmt::Display for TypePath<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.global {
+            f.write_str("::")?;
+        }
+        write_separated(f, &self.segments, "::")
+    }
+}
+
+impl fmt::Display for PathSegment<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        match &self.args {
+            Some(GenericArgs::AngleBracketed(args)) => {
+                f.write_str("<")?;
+                write_separated(f, args, ", ")?;
+                f.write_str(">")
+            }
+            Some(GenericArgs::Parenthesized { inputs, output }) => {
+                f.write_str("(")?;
+                write_separated(f, inputs, ", ")?;
+                f.write_str(")")?;
+                if let Some(output) = output {
+                    write!(f, " -> {}", output)?;
+                }
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl fmt::Display for GenericArg<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenericArg::Lifetime(lifetime) => f.write_str(lifetime),
+            GenericArg::Type(ty) => write!(f, "{}", ty),
+            GenericArg::Const(value) => f.write_str(value),
+            GenericArg::Binding { name, ty } => write!(f, "{} = {}", name, ty),
+            GenericArg::Constraint { name, bounds } => {
+                write!(f, "{}: ", name)?;
+                write_separated(f, bounds, " + ")
+            }
+        }
+    }
+}
+
+impl fmt::Display for Bound<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Bound::Trait {
+                for_lifetimes,
+                maybe,
+                path,
+            } => {
+                write_for_lifetimes(f, for_lifetimes)?;
+                if *maybe {
+                    f.write_str("?")?;
+                }
+                write!(f, "{}", path)
+            }
+            Bound::Lifetime(lifetime) => f.write_str(lifetime),
+        }
+    }
+}
+
+impl fmt::Display for FnPointer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_for_lifetimes(f, &self.for_lifetimes)?;
+        if self.unsafe_ {
+            f.write_str("unsafe ")?;
+        }
+        if let Some(abi) = &self.abi {
+            write!(f, "extern \"{}\" ", abi)?;
+        }
+        f.write_str("fn(")?;
+        write_separated(f, &self.inputs, ", ")?;
+        f.write_str(")")?;
+        if let Some(output) = &self.output {
+            write!(f, " -> {}", output)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for GenericParam<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenericParam::Lifetime { name, bounds } => {
+                f.write_str(name)?;
+                if !bounds.is_empty() {
+                    f.write_str(": ")?;
+                    write_separated(f, bounds, " + ")?;
+                }
+                Ok(())
+            }
+            GenericParam::Type {
+                name,
+                bounds,
+                default,
+            } => {
+                f.write_str(name)?;
+                if !bounds.is_empty() {
+                    f.write_str(": ")?;
+                    write_separated(f, bounds, " + ")?;
+                }
+                if let Some(default) = default {
+                    write!(f, " = {}", default)?;
+                }
+                Ok(())
+            }
+            GenericParam::Const { name, ty, default } => {
+                write!(f, "const {}: {}", name, ty)?;
+                if let Some(default) = default {
+                    write!(f, " = {}", default)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl fmt::Display for WherePredicate<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WherePredicate::Bound {
+                for_lifetimes,
+                ty,
+                bounds,
+            } => {
+                write_for_lifetimes(f, for_lifetimes)?;
+                write!(f, "{}: ", ty)?;
+                write_separated(f, bounds, " + ")
+            }
+            WherePredicate::Lifetime { lifetime, bounds } => {
+                write!(f, "{}: ", lifetime)?;
+                write_separated(f, bounds, " + ")
+            }
+        }
+    }
+}
+
+pub(crate) fn write_generics(
+    f: &mut fmt::Formatter<'_>,
+    generics: &[GenericParam<'_>],
+) -> fmt::Result {
+    if !generics.is_empty() {
+        f.write_str("<")?;
+        write_separated(f, generics, ", ")?;
+        f.write_str(">")?;
+    }
+    Ok(())
+}
+
+pub(crate) fn write_where_clause(
+    f: &mut fmt::Formatter<'_>,
+    predicates: &[WherePredicate<'_>],
+) -> fmt::Result {
+    if !predicates.is_empty() {
+        f.write_str(" where ")?;
+        write_separated(f, predicates, ", ")?;
+    }
+    Ok(())
+}
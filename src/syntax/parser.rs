@@ -0,0 +1,580 @@
+use std::borrow::Cow;
+
+use super::{
+    lexer::{Kind, Token},
+    Bound, FnPointer, GenericArg, GenericArgs, GenericParam, PathSegment, Type, TypePath,
+    WherePredicate,
+};
+
+pub struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    // Indices of the tokens that directly follow a block comment
+    comments: Vec<usize>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(tokens: Vec<Token<'a>>) -> Self {
+        let mut parser = Parser {
+            tokens: vec![],
+            comments: vec![],
+            position: 0,
+        };
+        for token in tokens {
+            if token.kind == Kind::Comment {
+                parser.comments.push(parser.tokens.len());
+            } else {
+                parser.tokens.push(token);
+            }
+        }
+        parser
+    }
+
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    pub fn has_comment_since(&self, start: usize) -> bool {
+        self.comments
+            .iter()
+            .any(|&index| start <= index && index < self.position)
+    }
+
+    pub fn is_end(&self) -> bool {
+        self.position >= self.tokens.len()
+    }
+
+    pub fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.position)
+    }
+
+    pub fn peek_nth(&self, n: usize) -> Option<&Token<'a>> {
+        self.tokens.get(self.position + n)
+    }
+
+    pub fn is(&self, text: &str) -> bool {
+        self.peek().is_some_and(|token| token.text == text)
+    }
+
+    pub fn is_kind(&self, kind: Kind) -> bool {
+        self.peek().is_some_and(|token| token.kind == kind)
+    }
+
+    pub fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    pub fn eat(&mut self, text: &str) -> bool {
+        if self.is(text) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect(&mut self, text: &str) -> Option<()> {
+        self.eat(text).then_some(())
+    }
+
+    pub fn expect_kind(&mut self, kind: Kind) -> Option<Token<'a>> {
+        if self.is_kind(kind) {
+            self.next()
+        } else {
+            None
+        }
+    }
+
+    pub fn skip_attributes(&mut self) {
+        while self.is("#") {
+            self.next();
+            self.eat("!");
+            if self.is("[") {
+                self.skip_group();
+            }
+        }
+    }
+
+    pub fn skip_group(&mut self) {
+        let mut depth = 0usize;
+        while let Some(token) = self.next() {
+            match &*token.text {
+                "(" | "[" | "{" => depth += 1,
+                ")" | "]" | "}" => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub fn parse_visibility(&mut self) -> Option<Cow<'a, str>> {
+        if !self.is("pub") {
+            return None;
+        }
+        let start = self.position;
+        self.next();
+        if self.is("(") {
+            self.skip_group();
+        }
+        Some(self.text_since(start))
+    }
+
+    pub fn text_since(&self, start: usize) -> Cow<'a, str> {
+        join(&self.tokens[start..self.position])
+    }
+
+    pub fn parse_raw_until(&mut self, ends: &[&str]) -> Cow<'a, str> {
+        let start = self.position;
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            match &*token.text {
+                "(" | "[" | "{" | "<" => depth += 1,
+                ")" | "]" | "}" | ">" if depth > 0 => depth -= 1,
+                text if depth == 0 && ends.contains(&text) => break,
+                _ => {}
+            }
+            self.position += 1;
+        }
+        self.text_since(start)
+    }
+
+    pub fn parse_type(&mut self) -> Option<Type<'a>> {
+        let token = self.peek()?;
+        match (token.kind, &*token.text) {
+            (Kind::Punct, "!") => {
+                self.next();
+                Some(Type::Never)
+            }
+            (Kind::Ident, "_") => {
+                self.next();
+                Some(Type::Infer)
+            }
+            (Kind::Punct, "(") => {
+                self.next();
+                let mut types = vec![];
+                let mut trailing = false;
+                while !self.eat(")") {
+                    types.push(self.parse_type()?);
+                    trailing = self.eat(",");
+                    if !trailing {
+                        self.expect(")")?;
+                        break;
+                    }
+                }
+                if types.len() == 1 && !trailing {
+                    types.pop()
+                } else {
+                    Some(Type::Tuple(types))
+                }
+            }
+            (Kind::Punct, "[") => {
+                self.next();
+                let ty = Box::new(self.parse_type()?);
+                if self.eat(";") {
+                    let len = self.parse_raw_until(&["]"]);
+                    self.expect("]")?;
+                    Some(Type::Array { ty, len })
+                } else {
+                    self.expect("]")?;
+                    Some(Type::Slice(ty))
+                }
+            }
+            (Kind::Punct, "&") => {
+                self.next();
+                let lifetime = self.expect_kind(Kind::Lifetime).map(|token| token.text);
+                let mutable = self.eat("mut");
+                let ty = Box::new(self.parse_type()?);
+                Some(Type::Reference {
+                    lifetime,
+                    mutable,
+                    ty,
+                })
+            }
+            (Kind::Punct, "*") => {
+                self.next();
+                let mutable = if self.eat("mut") {
+                    true
+                } else {
+                    self.expect("const")?;
+                    false
+                };
+                let ty = Box::new(self.parse_type()?);
+                Some(Type::Pointer { mutable, ty })
+            }
+            (Kind::Punct, "<") => self.parse_qualified_path(),
+            (Kind::Ident, "impl") => {
+                self.next();
+                Some(Type::ImplTrait(self.parse_bounds()?))
+            }
+            (Kind::Ident, "dyn") => {
+                self.next();
+                Some(Type::DynTrait(self.parse_bounds()?))
+            }
+            (Kind::Ident, "fn" | "unsafe" | "extern") => self.parse_fn_pointer(vec![]),
+            (Kind::Ident, "for") => {
+                let for_lifetimes = self.parse_for_lifetimes()?;
+                if self.is("fn") || self.is("unsafe") || self.is("extern") {
+                    self.parse_fn_pointer(for_lifetimes)
+                } else {
+                    let path = self.parse_path()?;
+                    Some(Type::DynTrait(vec![Bound::Trait {
+                        for_lifetimes,
+                        maybe: false,
+                        path,
+                    }]))
+                }
+            }
+            (Kind::Ident, _) | (Kind::Punct, "::") => Some(Type::Path(self.parse_path()?)),
+            _ => None,
+        }
+    }
+
+    fn parse_qualified_path(&mut self) -> Option<Type<'a>> {
+        self.expect("<")?;
+        let self_ty = Box::new(self.parse_type()?);
+        let trait_path = if self.eat("as") {
+            Some(self.parse_path()?)
+        } else {
+            None
+        };
+        self.expect(">")?;
+
+        let mut segments = vec![];
+        while self.eat("::") {
+            segments.push(self.parse_path_segment()?);
+        }
+
+        Some(Type::QualifiedPath {
+            self_ty,
+            trait_path,
+            segments,
+        })
+    }
+
+    fn parse_fn_pointer(&mut self, for_lifetimes: Vec<Cow<'a, str>>) -> Option<Type<'a>> {
+        let unsafe_ = self.eat("unsafe");
+        let abi = self.parse_abi();
+        self.expect("fn")?;
+        self.expect("(")?;
+        let mut inputs = vec![];
+        while !self.eat(")") {
+            if self.is_kind(Kind::Ident) && self.peek_nth(1).is_some_and(|next| next.text == ":") {
+                self.next();
+                self.next();
+            }
+            inputs.push(self.parse_type()?);
+            if !self.eat(",") {
+                self.expect(")")?;
+                break;
+            }
+        }
+        let output = self.parse_return_type()?;
+
+        Some(Type::FnPointer(Box::new(FnPointer {
+            for_lifetimes,
+            unsafe_,
+            abi,
+            inputs,
+            output,
+        })))
+    }
+
+    pub fn parse_abi(&mut self) -> Option<Cow<'a, str>> {
+        if !self.eat("extern") {
+            return None;
+        }
+        match self.peek() {
+            Some(token) if token.kind == Kind::Literal && token.text.starts_with('"') => {
+                let abi = self.next().unwrap().text;
+                Some(match abi {
+                    Cow::Borrowed(abi) => Cow::Borrowed(abi.trim_matches('"')),
+                    Cow::Owned(abi) => Cow::Owned(abi.trim_matches('"').to_string()),
+                })
+            }
+            _ => Some(Cow::Borrowed("C")),
+        }
+    }
+
+    pub fn parse_return_type(&mut self) -> Option<Option<Type<'a>>> {
+        if self.eat("->") {
+            Some(Some(self.parse_type()?))
+        } else {
+            Some(None)
+        }
+    }
+
+    pub fn parse_path(&mut self) -> Option<TypePath<'a>> {
+        let global = self.eat("::");
+        let mut segments = vec![self.parse_path_segment()?];
+        while self.is("::")
+            && self
+                .peek_nth(1)
+                .is_some_and(|next| next.kind == Kind::Ident)
+        {
+            self.next();
+            segments.push(self.parse_path_segment()?);
+        }
+        Some(TypePath { global, segments })
+    }
+
+    fn parse_path_segment(&mut self) -> Option<PathSegment<'a>> {
+        let name = self.expect_kind(Kind::Ident)?;
+
+        if self.is("::") && self.peek_nth(1).is_some_and(|next| next.text == "<") {
+            self.next();
+        }
+        let args = if self.is("<") {
+            Some(self.parse_angle_args()?)
+        } else if self.is("(") && is_fn_trait(&name.text) {
+            self.next();
+            let mut inputs = vec![];
+            while !self.eat(")") {
+                inputs.push(self.parse_type()?);
+                if !self.eat(",") {
+                    self.expect(")")?;
+                    break;
+                }
+            }
+            let output = self.parse_return_type()?.map(Box::new);
+            Some(GenericArgs::Parenthesized { inputs, output })
+        } else {
+            None
+        };
+
+        Some(PathSegment {
+            name: name.text,
+            href: name.href,
+            args,
+        })
+    }
+
+    fn parse_angle_args(&mut self) -> Option<GenericArgs<'a>> {
+        self.expect("<")?;
+        let mut args = vec![];
+        while !self.eat(">") {
+            args.push(self.parse_generic_arg()?);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Some(GenericArgs::AngleBracketed(args))
+    }
+
+    fn parse_generic_arg(&mut self) -> Option<GenericArg<'a>> {
+        let token = self.peek()?;
+        match token.kind {
+            Kind::Lifetime => Some(GenericArg::Lifetime(self.next()?.text)),
+            Kind::Literal => Some(GenericArg::Const(self.next()?.text)),
+            Kind::Punct if token.text == "{" || token.text == "-" => {
+                Some(GenericArg::Const(self.parse_raw_until(&[",", ">"])))
+            }
+            Kind::Ident
+                if self
+                    .peek_nth(1)
+                    .is_some_and(|next| next.text == "=" || next.text == ":") =>
+            {
+                let name = self.next()?.text;
+                if self.eat("=") {
+                    Some(GenericArg::Binding {
+                        name,
+                        ty: self.parse_type()?,
+                    })
+                } else {
+                    self.expect(":")?;
+                    Some(GenericArg::Constraint {
+                        name,
+                        bounds: self.parse_bounds()?,
+                    })
+                }
+            }
+            _ => Some(GenericArg::Type(self.parse_type()?)),
+        }
+    }
+
+    pub fn parse_for_lifetimes(&mut self) -> Option<Vec<Cow<'a, str>>> {
+        if !self.eat("for") {
+            return Some(vec![]);
+        }
+        self.expect("<")?;
+        let mut lifetimes = vec![];
+        while !self.eat(">") {
+            lifetimes.push(self.expect_kind(Kind::Lifetime)?.text);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Some(lifetimes)
+    }
+
+    pub fn parse_bounds(&mut self) -> Option<Vec<Bound<'a>>> {
+        let mut bounds = vec![self.parse_bound()?];
+        while self.eat("+") {
+            bounds.push(self.parse_bound()?);
+        }
+        Some(bounds)
+    }
+
+    fn parse_bound(&mut self) -> Option<Bound<'a>> {
+        if self.is_kind(Kind::Lifetime) {
+            return Some(Bound::Lifetime(self.next()?.text));
+        }
+        if self.eat("(") {
+            let bound = self.parse_bound()?;
+            self.expect(")")?;
+            return Some(bound);
+        }
+        let for_lifetimes = self.parse_for_lifetimes()?;
+        if self.eat("~") {
+            self.expect("const")?;
+        }
+        let maybe = self.eat("?");
+        let path = self.parse_path()?;
+        Some(Bound::Trait {
+            for_lifetimes,
+            maybe,
+            path,
+        })
+    }
+
+    fn parse_lifetime_bounds(&mut self) -> Option<Vec<Cow<'a, str>>> {
+        let mut bounds = vec![self.expect_kind(Kind::Lifetime)?.text];
+        while self.eat("+") {
+            bounds.push(self.expect_kind(Kind::Lifetime)?.text);
+        }
+        Some(bounds)
+    }
+
+    pub fn parse_generics(&mut self) -> Option<Vec<GenericParam<'a>>> {
+        if !self.eat("<") {
+            return Some(vec![]);
+        }
+        let mut params = vec![];
+        while !self.eat(">") {
+            self.skip_attributes();
+            params.push(self.parse_generic_param()?);
+            if !self.eat(",") {
+                self.expect(">")?;
+                break;
+            }
+        }
+        Some(params)
+    }
+
+    fn parse_generic_param(&mut self) -> Option<GenericParam<'a>> {
+        if self.is_kind(Kind::Lifetime) {
+            let name = self.next()?.text;
+            let bounds = if self.eat(":") {
+                self.parse_lifetime_bounds()?
+            } else {
+                vec![]
+            };
+            return Some(GenericParam::Lifetime { name, bounds });
+        }
+
+        if self.eat("const") {
+            let name = self.expect_kind(Kind::Ident)?.text;
+            self.expect(":")?;
+            let ty = self.parse_type()?;
+            let default = if self.eat("=") {
+                Some(self.parse_raw_until(&[",", ">"]))
+            } else {
+                None
+            };
+            return Some(GenericParam::Const { name, ty, default });
+        }
+
+        let name = self.expect_kind(Kind::Ident)?.text;
+        let bounds = if self.eat(":") {
+            if self.is(",") || self.is(">") || self.is("=") {
+                vec![]
+            } else {
+                self.parse_bounds()?
+            }
+        } else {
+            vec![]
+        };
+        let default = if self.eat("=") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+        Some(GenericParam::Type {
+            name,
+            bounds,
+            default,
+        })
+    }
+
+    pub fn parse_where_clause(&mut self) -> Option<Vec<WherePredicate<'a>>> {
+        if !self.eat("where") {
+            return Some(vec![]);
+        }
+        let mut predicates = vec![];
+        while !self.is_end() && !self.is("{") && !self.is(";") && !self.is("=") {
+            predicates.push(self.parse_where_predicate()?);
+            if !self.eat(",") {
+                break;
+            }
+        }
+        Some(predicates)
+    }
+
+    fn parse_where_predicate(&mut self) -> Option<WherePredicate<'a>> {
+        if self.is_kind(Kind::Lifetime) {
+            let lifetime = self.next()?.text;
+            self.expect(":")?;
+            let bounds = self.parse_lifetime_bounds()?;
+            return Some(WherePredicate::Lifetime { lifetime, bounds });
+        }
+
+        let for_lifetimes = if self.is("for") && self.peek_nth(1).is_some_and(|t| t.text == "<") {
+            self.parse_for_lifetimes()?
+        } else {
+            vec![]
+        };
+        let ty = self.parse_type()?;
+        self.expect(":")?;
+        let bounds = if self.is(",") || self.is_end() {
+            vec![]
+        } else {
+            self.parse_bounds()?
+        };
+        Some(WherePredicate::Bound {
+            for_lifetimes,
+            ty,
+            bounds,
+        })
+    }
+}
+
+fn is_fn_trait(name: &str) -> bool {
+    matches!(name, "Fn" | "FnMut" | "FnOnce")
+}
+
+fn join<'a>(tokens: &[Token<'a>]) -> Cow<'a, str> {
+    if let [token] = tokens {
+        return token.text.clone();
+    }
+
+    let mut text = String::new();
+    let mut previous: Option<&Token> = None;
+    for token in tokens {
+        if let Some(previous) = previous {
+            let is_word = |token: &Token| token.kind != Kind::Punct;
+            if (is_word(previous) && is_word(token)) || previous.text == "," {
+                text.push(' ');
+            }
+        }
+        text.push_str(&token.text);
+        previous = Some(token);
+    }
+    Cow::Owned(text)
+}
@@ -0,0 +1,398 @@
+use std::{borrow::Cow, fmt};
+
+use crate::atom::{into_owned, TextPart};
+
+use super::{
+    into_owned_generics, into_owned_where_clause,
+    lexer::{tokenize, Kind},
+    parser::Parser,
+    write_generics, write_separated, write_where_clause, GenericParam, Type, WherePredicate,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Signature<'a> {
+    pub visibility: Option<Cow<'a, str>>,
+    pub qualifiers: Qualifiers<'a>,
+    pub name: Cow<'a, str>,
+    pub generics: Vec<GenericParam<'a>>,
+    pub receiver: Option<Receiver<'a>>,
+    pub params: Vec<Param<'a>>,
+    pub variadic: bool,
+    pub output: Option<Type<'a>>,
+    pub where_clause: Vec<WherePredicate<'a>>,
+}
+
+impl Signature<'_> {
+    pub fn into_owned(self) -> Signature<'static> {
+        Signature {
+            visibility: self.visibility.map(into_owned),
+            qualifiers: self.qualifiers.into_owned(),
+            name: into_owned(self.name),
+            generics: into_owned_generics(self.generics),
+            receiver: self.receiver.map(Receiver::into_owned),
+            params: self.params.into_iter().map(Param::into_owned).collect(),
+            variadic: self.variadic,
+            output: self.output.map(Type::into_owned),
+            where_clause: into_owned_where_clause(self.where_clause),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Qualifiers<'a> {
+    pub default: bool,
+    pub const_: bool,
+    pub async_: bool,
+    pub unsafe_: bool,
+    pub abi: Option<Cow<'a, str>>,
+}
+
+impl Qualifiers<'_> {
+    pub fn into_owned(self) -> Qualifiers<'static> {
+        Qualifiers {
+            default: self.default,
+            const_: self.const_,
+            async_: self.async_,
+            unsafe_: self.unsafe_,
+            abi: self.abi.map(into_owned),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", content = "value", rename_all = "snake_case")
+)]
+pub enum Receiver<'a> {
+    Value {
+        mutable: bool,
+    },
+    Reference {
+        lifetime: Option<Cow<'a, str>>,
+        mutable: bool,
+    },
+    Typed {
+        mutable: bool,
+        ty: Type<'a>,
+    },
+}
+
+impl Receiver<'_> {
+    pub fn into_owned(self) -> Receiver<'static> {
+        match self {
+            Receiver::Value { mutable } => Receiver::Value { mutable },
+            Receiver::Reference { lifetime, mutable } => Receiver::Reference {
+                lifetime: lifetime.map(into_owned),
+                mutable,
+            },
+            Receiver::Typed { mutable, ty } => Receiver::Typed {
+                mutable,
+                ty: ty.into_owned(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Param<'a> {
+    pub pattern: Cow<'a, str>,
+    pub ty: Type<'a>,
+}
+
+impl Param<'_> {
+    pub fn into_owned(self) -> Param<'static> {
+        Param {
+            pattern: into_owned(self.pattern),
+            ty: self.ty.into_owned(),
+        }
+    }
+}
+
+pub fn parse_signature<'a>(header: &[TextPart<'a>]) -> Option<Signature<'a>> {
+    let mut parser = Parser::new(tokenize(header));
+    let signature = parse_fn(&mut parser)?;
+    parser.is_end().then_some(signature)
+}
+
+pub(super) fn parse_fn<'a>(parser: &mut Parser<'a>) -> Option<Signature<'a>> {
+    parser.skip_attributes();
+    let visibility = parser.parse_visibility();
+
+    let mut qualifiers = Qualifiers::default();
+    loop {
+        if parser.eat("default") {
+            qualifiers.default = true;
+        } else if parser.eat("const") {
+            qualifiers.const_ = true;
+        } else if parser.eat("async") {
+            qualifiers.async_ = true;
+        } else if parser.eat("unsafe") {
+            qualifiers.unsafe_ = true;
+        } else if parser.is("extern") {
+            qualifiers.abi = parser.parse_abi();
+        } else {
+            break;
+        }
+    }
+
+    parser.expect("fn")?;
+    let name = parser.expect_kind(Kind::Ident)?.text;
+    let generics = parser.parse_generics()?;
+
+    parser.expect("(")?;
+    let receiver = parse_receiver(parser)?;
+    let mut params = vec![];
+    let mut variadic = false;
+    if receiver.is_none() || parser.eat(",") {
+        while !parser.eat(")") {
+            if parser.eat("...") {
+                variadic = true;
+            } else {
+                let pattern = parser.parse_raw_until(&[":"]);
+                parser.expect(":")?;
+                let ty = parser.parse_type()?;
+                params.push(Param { pattern, ty });
+            }
+            if !parser.eat(",") {
+                parser.expect(")")?;
+                break;
+            }
+        }
+    } else {
+        parser.expect(")")?;
+    }
+
+    let output = parser.parse_return_type()?;
+    let where_clause = parser.parse_where_clause()?;
+
+    Some(Signature {
+        visibility,
+        qualifiers,
+        name,
+        generics,
+        receiver,
+        params,
+        variadic,
+        output,
+        where_clause,
+    })
+}
+
+fn parse_receiver<'a>(parser: &mut Parser<'a>) -> Option<Option<Receiver<'a>>> {
+    let is_self = |n: usize| parser.peek_nth(n).is_some_and(|token| token.text == "self");
+
+    if parser.is("&") {
+        let mut offset = 1;
+        let lifetime = parser
+            .peek_nth(offset)
+            .filter(|token| token.kind == Kind::Lifetime)
+            .map(|token| token.text.clone());
+        if lifetime.is_some() {
+            offset += 1;
+        }
+        let mutable = parser
+            .peek_nth(offset)
+            .is_some_and(|token| token.text == "mut");
+        if mutable {
+            offset += 1;
+        }
+        if !is_self(offset) {
+            return Some(None);
+        }
+        for _ in 0..=offset {
+            parser.next();
+        }
+        return Some(Some(Receiver::Reference { lifetime, mutable }));
+    }
+
+    let mutable = parser.is("mut");
+    if !is_self(mutable as usize) {
+        return Some(None);
+    }
+    parser.eat("mut");
+    parser.next();
+
+    if parser.eat(":") {
+        let ty = parser.parse_type()?;
+        Some(Some(Receiver::Typed { mutable, ty }))
+    } else {
+        Some(Some(Receiver::Value { mutable }))
+    }
+}
+
+impl fmt::Display for Signature<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(visibility) = &self.visibility {
+            write!(f, "{} ", visibility)?;
+        }
+        write!(f, "{}fn {}", self.qualifiers, self.name)?;
+        write_generics(f, &self.generics)?;
+        f.write_str("(")?;
+        if let Some(receiver) = &self.receiver {
+            write!(f, "{}", receiver)?;
+            if !self.params.is_empty() {
+                f.write_str(", ")?;
+            }
+        }
+        write_separated(f, &self.params, ", ")?;
+        if self.variadic {
+            if self.receiver.is_some() || !self.params.is_empty() {
+                f.write_str(", ")?;
+            }
+            f.write_str("...")?;
+        }
+        f.write_str(")")?;
+        if let Some(output) = &self.output {
+            write!(f, " -> {}", output)?;
+        }
+        write_where_clause(f, &self.where_clause)
+    }
+}
+
+impl fmt::Display for Qualifiers<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.default {
+            f.write_str("default ")?;
+        }
+        if self.const_ {
+            f.write_str("const ")?;
+        }
+        if self.async_ {
+            f.write_str("async ")?;
+        }
+        if self.unsafe_ {
+            f.write_str("unsafe ")?;
+        }
+        if let Some(abi) = &self.abi {
+            write!(f, "extern \"{}\" ", abi)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Receiver<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Receiver::Value { mutable } => {
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                f.write_str("self")
+            }
+            Receiver::Reference { lifetime, mutable } => {
+                f.write_str("&")?;
+                if let Some(lifetime) = lifetime {
+                    write!(f, "{} ", lifetime)?;
+                }
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                f.write_str("self")
+            }
+            Receiver::Typed { mutable, ty } => {
+                if *mutable {
+                    f.write_str("mut ")?;
+                }
+                write!(f, "self: {}", ty)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Param<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.pattern, self.ty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use crate::atom::TextPart;
+
+    use super::{parse_signature, Receiver, Signature};
+
+    fn parse(source: &str) -> Option<Signature<'_>> {
+        parse_signature(&[TextPart::Text(Cow::Borrowed(source))])
+    }
+
+    #[test]
+    fn qualifiers_and_params() {
+        let signature =
+            parse(r#"pub const unsafe extern "C" fn from_raw_parts<'a, T>(data: *const T, len: usize) -> &'a [T]"#)
+                .unwrap();
+        assert_eq!(signature.visibility.as_deref(), Some("pub"));
+        assert!(signature.qualifiers.const_);
+        assert!(signature.qualifiers.unsafe_);
+        assert!(!signature.qualifiers.async_);
+        assert_eq!(signature.qualifiers.abi.as_deref(), Some("C"));
+        assert_eq!(signature.name, "from_raw_parts");
+        assert_eq!(signature.generics.len(), 2);
+        assert_eq!(signature.receiver, None);
+        let patterns: Vec<_> = signature
+            .params
+            .iter()
+            .map(|param| &*param.pattern)
+            .collect();
+        assert_eq!(patterns, ["data", "len"]);
+        assert!(signature.output.is_some());
+        assert_eq!(
+            signature.to_string(),
+            r#"pub const unsafe extern "C" fn from_raw_parts<'a, T>(data: *const T, len: usize) -> &'a [T]"#
+        );
+    }
+
+    #[test]
+    fn receivers() {
+        let receiver = |source| parse(source).unwrap().receiver;
+
+        assert_eq!(
+            receiver("fn len(self)"),
+            Some(Receiver::Value { mutable: false })
+        );
+        assert_eq!(
+            receiver("fn push(mut self)"),
+            Some(Receiver::Value { mutable: true })
+        );
+        assert_eq!(
+            receiver("fn get_mut(&'a mut self)"),
+            Some(Receiver::Reference {
+                lifetime: Some(Cow::Borrowed("'a")),
+                mutable: true,
+            })
+        );
+        assert!(matches!(
+            receiver("fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>)"),
+            Some(Receiver::Typed { mutable: false, .. })
+        ));
+        assert_eq!(receiver("fn new(value: &T)"), None);
+    }
+
+    #[test]
+    fn variadic_and_where_clause() {
+        let signature =
+            parse("pub unsafe extern \"C\" fn printf(format: *const c_char, ...) -> c_int")
+                .unwrap();
+        assert!(signature.variadic);
+        assert_eq!(signature.params.len(), 1);
+
+        let signature = parse("pub fn black_box<T>(dummy: T) -> T where T: Sized").unwrap();
+        assert_eq!(signature.where_clause.len(), 1);
+        assert_eq!(
+            signature.to_string(),
+            "pub fn black_box<T>(dummy: T) -> T where T: Sized"
+        );
+    }
+
+    #[test]
+    fn rejects_trailing_tokens() {
+        assert!(parse("fn lock(&self) { ... }").is_none());
+        assert!(parse("struct Lock;").is_none());
+    }
+}
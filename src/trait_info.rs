@@ -0,0 +1,95 @@
+use crate::{
+    item::{impls::ImplCategory, Impl, Item},
+    kind::ItemKind,
+    syntax::declaration::{parse_trait_item, TraitItem},
+    Document, ListingType,
+};
+
+/// The listings of a trait page grouped by role. Implementors are split into local impls, auto
+/// trait impls and impls on foreign types.
+#[derive(Debug, Clone, Default)]
+pub struct TraitInfo<'d, 'a> {
+    pub required_methods: Vec<&'d Item<'a>>,
+    pub provided_methods: Vec<&'d Item<'a>>,
+    pub associated_types: Vec<AssociatedItem<'d, 'a>>,
+    pub associated_consts: Vec<AssociatedItem<'d, 'a>>,
+    pub implementors: Vec<&'d Impl<'a>>,
+    pub auto_implementors: Vec<&'d Impl<'a>>,
+    pub foreign_impls: Vec<&'d Impl<'a>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AssociatedItem<'d, 'a> {
+    pub item: &'d Item<'a>,
+    pub declaration: Option<TraitItem<'a>>,
+    pub required: bool,
+}
+
+impl<'a> Document<'a> {
+    pub fn trait_info(&self) -> Option<TraitInfo<'_, 'a>> {
+        if self.kind() != Some(ItemKind::Trait) {
+            return None;
+        }
+
+        let mut info = TraitInfo::default();
+        for listing in &self.items {
+            match &listing.kind {
+                ListingType::Fields(items) => {
+                    let section = listing.anchor.as_deref().unwrap_or_default();
+                    for item in items {
+                        info.push_item(item, section);
+                    }
+                }
+                ListingType::Impls(impls) => {
+                    let target = match listing.category {
+                        Some(ImplCategory::Implementors) => &mut info.implementors,
+                        Some(ImplCategory::AutoImplementors) => &mut info.auto_implementors,
+                        Some(ImplCategory::Foreign) => &mut info.foreign_impls,
+                        _ => continue,
+                    };
+                    target.extend(impls);
+                }
+                ListingType::Table(_) => {}
+            }
+        }
+        Some(info)
+    }
+}
+
+impl<'d, 'a> TraitInfo<'d, 'a> {
+    fn push_item(&mut self, item: &'d Item<'a>, section: &str) {
+        let anchor = item.anchor.as_deref().unwrap_or_default();
+        let kind = anchor.split('.').next().unwrap_or_default();
+
+        match kind {
+            "tymethod" => self.required_methods.push(item),
+            "method" => self.provided_methods.push(item),
+            "associatedtype" | "associatedconstant" => {
+                let declaration = parse_trait_item(&item.name);
+                // Older rustdoc puts required and provided items under a single heading.
+                let required = if section.starts_with("required-") {
+                    true
+                } else if section.starts_with("provided-") {
+                    false
+                } else {
+                    match &declaration {
+                        Some(TraitItem::Type { default, .. }) => default.is_none(),
+                        Some(TraitItem::Const { default, .. }) => default.is_none(),
+                        _ => true,
+                    }
+                };
+                let associated = AssociatedItem {
+                    item,
+                    declaration,
+                    required,
+                };
+                if kind == "associatedtype" {
+                    self.associated_types.push(associated);
+                } else {
+                    self.associated_consts.push(associated);
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+pub fn resolve_url<'u>(base: &str, url: &'u str) -> Cow<'u, str> {
+    if has_scheme(url) {
+        return Cow::Borrowed(url);
+    }
+
+    let (origin, base_path) = match base.find("://") {
+        Some(index) => {
+            let host = index + "://".len();
+            let end = base[host..]
+                .find(['/', '?', '#'])
+                .map_or(base.len(), |end| host + end);
+            base.split_at(end)
+        }
+        None => ("", base),
+    };
+
+    if let Some(rest) = url.strip_prefix("//") {
+        return match origin.split_once("://") {
+            Some((scheme, _)) => Cow::Owned(format!("{}://{}", scheme, rest)),
+            None => Cow::Borrowed(url),
+        };
+    }
+
+    let base_path = base_path.split('#').next().unwrap_or_default();
+    if url.is_empty() {
+        return Cow::Owned(format!("{}{}", origin, base_path));
+    }
+    if url.starts_with('#') {
+        return Cow::Owned(format!("{}{}{}", origin, base_path, url));
+    }
+    let base_path = base_path.split('?').next().unwrap_or_default();
+    if url.starts_with('?') {
+        return Cow::Owned(format!("{}{}{}", origin, base_path, url));
+    }
+
+    let (path, suffix) = url.split_at(url.find(['?', '#']).unwrap_or(url.len()));
+    let path = if path.starts_with('/') {
+        normalize_path(path)
+    } else {
+        // A base with a host but no path, such as `https://docs.rs`, resolves against `/`
+        let directory = match base_path.rfind('/') {
+            Some(end) => &base_path[..=end],
+            None if !origin.is_empty() => "/",
+            None => "",
+        };
+        normalize_path(&format!("{}{}", directory, path))
+    };
+
+    Cow::Owned(format!("{}{}{}", origin, path, suffix))
+}
+
+fn has_scheme(url: &str) -> bool {
+    url.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    })
+}
+
+fn normalize_path(path: &str) -> String {
+    let mut segments: Vec<&str> = vec![];
+    for segment in path.split('/') {
+        match segment {
+            "." => {}
+            ".." => match segments.last() {
+                Some(&"") if segments.len() == 1 => {}
+                Some(&"..") | None => segments.push(".."),
+                Some(_) => {
+                    segments.pop();
+                }
+            },
+            _ => segments.push(segment),
+        }
+    }
+
+    let mut normalized = segments.join("/");
+    if path.ends_with("/.") || path.ends_with("/..") {
+        normalized.push('/');
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::resolve_url;
+
+    const BASE: &str = "http://a/b/c/d;p?q";
+
+    fn check(cases: &[(&str, &str)]) {
+        for (url, expected) in cases {
+            assert_eq!(resolve_url(BASE, url), *expected, "resolving {:?}", url);
+        }
+    }
+
+    // RFC 3986, section 5.4.1
+    #[test]
+    fn normal_examples() {
+        check(&[
+            ("g:h", "g:h"),
+            ("g", "http://a/b/c/g"),
+            ("./g", "http://a/b/c/g"),
+            ("g/", "http://a/b/c/g/"),
+            ("/g", "http://a/g"),
+            ("//g", "http://g"),
+            ("?y", "http://a/b/c/d;p?y"),
+            ("g?y", "http://a/b/c/g?y"),
+            ("#s", "http://a/b/c/d;p?q#s"),
+            ("g#s", "http://a/b/c/g#s"),
+            ("g?y#s", "http://a/b/c/g?y#s"),
+            (";x", "http://a/b/c/;x"),
+            ("g;x", "http://a/b/c/g;x"),
+            ("g;x?y#s", "http://a/b/c/g;x?y#s"),
+            ("", "http://a/b/c/d;p?q"),
+            (".", "http://a/b/c/"),
+            ("./", "http://a/b/c/"),
+            ("..", "http://a/b/"),
+            ("../", "http://a/b/"),
+            ("../g", "http://a/b/g"),
+            ("../..", "http://a/"),
+            ("../../", "http://a/"),
+            ("../../g", "http://a/g"),
+        ]);
+    }
+
+    // RFC 3986, section 5.4.2
+    #[test]
+    fn abnormal_examples() {
+        check(&[
+            ("../../../g", "http://a/g"),
+            ("../../../../g", "http://a/g"),
+            ("/./g", "http://a/g"),
+            ("/../g", "http://a/g"),
+            ("g.", "http://a/b/c/g."),
+            (".g", "http://a/b/c/.g"),
+            ("g..", "http://a/b/c/g.."),
+            ("..g", "http://a/b/c/..g"),
+            ("./../g", "http://a/b/g"),
+            ("./g/.", "http://a/b/c/g/"),
+            ("g/./h", "http://a/b/c/g/h"),
+            ("g/../h", "http://a/b/c/h"),
+            ("g;x=1/./y", "http://a/b/c/g;x=1/y"),
+            ("g;x=1/../y", "http://a/b/c/y"),
+            ("g?y/./x", "http://a/b/c/g?y/./x"),
+            ("g?y/../x", "http://a/b/c/g?y/../x"),
+            ("g#s/./x", "http://a/b/c/g#s/./x"),
+            ("g#s/../x", "http://a/b/c/g#s/../x"),
+        ]);
+    }
+
+    #[test]
+    fn base_without_path() {
+        assert_eq!(
+            resolve_url("https://docs.rs", "a.png"),
+            "https://docs.rs/a.png"
+        );
+        assert_eq!(
+            resolve_url("https://docs.rs", "./a.png"),
+            "https://docs.rs/a.png"
+        );
+        assert_eq!(
+            resolve_url("https://docs.rs", "../a.png"),
+            "https://docs.rs/a.png"
+        );
+        assert_eq!(
+            resolve_url("https://docs.rs", "/a.png"),
+            "https://docs.rs/a.png"
+        );
+    }
+
+    #[test]
+    fn empty_url_drops_the_fragment() {
+        assert_eq!(
+            resolve_url("https://doc.rust-lang.org/std/index.html?search=x#main", ""),
+            "https://doc.rust-lang.org/std/index.html?search=x"
+        );
+    }
+
+    #[test]
+    fn relative_base() {
+        assert_eq!(
+            resolve_url(
+                "std/hint/fn.black_box.html",
+                "../../static.files/rust-logo.svg"
+            ),
+            "static.files/rust-logo.svg"
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use scraper::{Html, Selector};
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RustdocVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub channel: Channel,
+    pub date: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Channel {
+    Nightly,
+    Beta,
+    Stable,
+}
+
+impl RustdocVersion {
+    pub fn at_least(&self, major: u32, minor: u32) -> bool {
+        (self.major, self.minor) >= (major, minor)
+    }
+}
+
+impl fmt::Display for RustdocVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        match self.channel {
+            Channel::Nightly => f.write_str("-nightly")?,
+            Channel::Beta => f.write_str("-beta")?,
+            Channel::Stable => {}
+        }
+        if let Some(date) = &self.date {
+            write!(f, " ({})", date)?;
+        }
+        Ok(())
+    }
+}
+
+const RESOURCE_NAMES: &[&str] = &[
+    "rustdoc",
+    "main",
+    "storage",
+    "settings",
+    "search-index",
+    "search",
+    "normalize",
+    "noscript",
+    "light",
+    "dark",
+    "ayu",
+    "source-script",
+    "crates",
+];
+
+pub fn detect_version(html: &Html) -> Option<RustdocVersion> {
+    let select_version = Selector::parse("[data-rustdoc-version]").unwrap();
+    let from_vars = html
+        .select(&select_version)
+        .filter_map(|element| element.value().attr("data-rustdoc-version"))
+        .find_map(parse_version_string);
+    if from_vars.is_some() {
+        return from_vars;
+    }
+
+    let select_generator = Selector::parse("meta[name=\"generator\"]").unwrap();
+    let from_generator = html
+        .select(&select_generator)
+        .filter_map(|element| element.value().attr("content"))
+        .filter_map(|content| content.strip_prefix("rustdoc "))
+        .find_map(parse_version_string);
+    if from_generator.is_some() {
+        return from_generator;
+    }
+
+    let select_resources = Selector::parse("link[href], script[src], #rustdoc-vars").unwrap();
+    html.select(&select_resources)
+        .flat_map(|element| element.value().attrs().map(|(_, value)| value))
+        .find_map(parse_resource_url)
+}
+
+fn parse_version_string(version: &str) -> Option<RustdocVersion> {
+    let mut parts = version.trim().splitn(2, ' ');
+    let mut parsed = parse_version(parts.next()?)?;
+    if let Some(build) = parts.next() {
+        parsed.date = build
+            .trim_matches(|c| c == '(' || c == ')')
+            .split(' ')
+            .find(|part| part.len() == 10 && part.as_bytes()[4] == b'-')
+            .map(str::to_string);
+    }
+    Some(parsed)
+}
+
+fn parse_version(version: &str) -> Option<RustdocVersion> {
+    let (number, channel) = match version.split_once('-') {
+        Some((number, channel)) if channel.starts_with("nightly") => (number, Channel::Nightly),
+        Some((number, channel)) if channel.starts_with("beta") => (number, Channel::Beta),
+        Some(_) => return None,
+        None => (version, Channel::Stable),
+    };
+
+    let mut numbers = number.split('.').map(|part| part.parse().ok());
+    let major = numbers.next()??;
+    let minor = numbers.next()??;
+    let patch = numbers.next()??;
+    if numbers.next().is_some() {
+        return None;
+    }
+
+    Some(RustdocVersion {
+        major,
+        minor,
+        patch,
+        channel,
+        date: None,
+    })
+}
+
+fn parse_resource_url(url: &str) -> Option<RustdocVersion> {
+    let file = url.rsplit('/').next()?;
+    let stem = file
+        .strip_suffix(".css")
+        .or_else(|| file.strip_suffix(".js"))?;
+
+    RESOURCE_NAMES
+        .iter()
+        .filter_map(|name| stem.strip_prefix(name))
+        .find_map(parse_resource_suffix)
+}
+
+fn parse_resource_suffix(suffix: &str) -> Option<RustdocVersion> {
+    let suffix = match suffix.strip_prefix('-') {
+        Some(suffix) => suffix,
+        None => return parse_version(suffix),
+    };
+
+    let mut parts = suffix.split('-');
+    let date = parts.next()?;
+    if !(date.len() == 8 && date.bytes().all(|b| b.is_ascii_digit())) {
+        return None;
+    }
+
+    let mut version = parse_version(parts.next()?)?;
+    match parts.next() {
+        Some("nightly") => version.channel = Channel::Nightly,
+        Some("beta") => version.channel = Channel::Beta,
+        _ => {}
+    }
+    version.date = Some(format!("{}-{}-{}", &date[..4], &date[4..6], &date[6..]));
+
+    Some(version)
+}
@@ -0,0 +1,64 @@
+use paradocs::{parse_document, Html, Paragraph};
+
+const WARNING: &str = "<div class=\"warning\">\n<p>Note however";
+
+fn with_black_box_warning(class: &str, check: impl FnOnce(&Paragraph)) {
+    let source = include_str!("../examples/std_hint_black_box.html").replace(
+        WARNING,
+        &WARNING.replace("class=\"warning\"", &format!("class=\"{}\"", class)),
+    );
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+
+    let paragraph = document
+        .description
+        .iter()
+        .flat_map(|section| &section.contents)
+        .find(|paragraph| {
+            matches!(
+                paragraph,
+                Paragraph::Admonition { .. } | Paragraph::RawHtml(_)
+            )
+        })
+        .unwrap();
+    check(paragraph);
+}
+
+#[test]
+fn warning_is_an_admonition() {
+    with_black_box_warning("warning", |paragraph| match paragraph {
+        Paragraph::Admonition { kind, contents } => {
+            assert_eq!(kind, "warning");
+            assert!(matches!(
+                contents.as_slice(),
+                [Paragraph::Text(_), Paragraph::Text(_)]
+            ));
+        }
+        paragraph => panic!("unexpected paragraph {:?}", paragraph),
+    });
+}
+
+#[test]
+fn kind_is_the_first_admonition_class_in_source_order() {
+    for class in ["note", "custom note warning", "important tip"] {
+        let expected = class
+            .split_whitespace()
+            .find(|class| *class != "custom")
+            .unwrap();
+        with_black_box_warning(class, |paragraph| match paragraph {
+            Paragraph::Admonition { kind, .. } => assert_eq!(kind, expected),
+            paragraph => panic!("unexpected paragraph {:?}", paragraph),
+        });
+    }
+}
+
+#[test]
+fn other_classed_divs_are_raw_html() {
+    with_black_box_warning("example-wrap", |paragraph| match paragraph {
+        Paragraph::RawHtml(html) => {
+            assert!(html.starts_with("<div class=\"example-wrap\">"));
+            assert!(html.contains("best-effort"));
+        }
+        paragraph => panic!("unexpected paragraph {:?}", paragraph),
+    });
+}
@@ -0,0 +1,125 @@
+use paradocs::{parse_document, AnchorTarget, Html};
+
+#[test]
+fn listing_anchors_resolve_to_listings() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+
+    for anchor in ["implementations", "#trait-implementations", "variants"] {
+        match document.find_by_anchor(anchor) {
+            Some(AnchorTarget::Listing(listing)) => {
+                assert_eq!(
+                    listing.anchor.as_deref(),
+                    Some(anchor.trim_start_matches('#'))
+                );
+            }
+            other => panic!("{} resolved to {:?}", anchor, other),
+        }
+    }
+    assert!(matches!(
+        document.find_by_anchor("examples"),
+        Some(AnchorTarget::Section(_))
+    ));
+}
+
+#[test]
+fn foreign_impls_listing_on_trait_page() {
+    let html = Html::parse_document(include_str!("../examples/serde_core_de_seq_access.html"));
+    let document = parse_document(&html).unwrap();
+
+    assert!(matches!(
+        document.find_by_anchor("foreign-impls"),
+        Some(AnchorTarget::Listing(_))
+    ));
+    assert!(document.find_by_anchor("no-such-anchor").is_none());
+}
+
+#[test]
+fn item_anchors_resolve_to_items() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+
+    for anchor in [
+        "method.is_some",
+        "#method.as_deref",
+        "variant.None",
+        "variant.Some",
+    ] {
+        match document.find_by_anchor(anchor) {
+            Some(AnchorTarget::Item(item)) => {
+                assert_eq!(item.anchor.as_deref(), Some(anchor.trim_start_matches('#')));
+            }
+            other => panic!("{} resolved to {:?}", anchor, other),
+        }
+    }
+
+    let html = Html::parse_document(include_str!("../examples/scraper_html_html.html"));
+    let document = parse_document(&html).unwrap();
+    for anchor in ["structfield.errors", "structfield.tree"] {
+        assert!(
+            matches!(document.find_by_anchor(anchor), Some(AnchorTarget::Item(item)) if item.anchor.as_deref() == Some(anchor)),
+            "{}",
+            anchor
+        );
+    }
+}
+
+#[test]
+fn impl_anchors_resolve_to_impls() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+
+    for anchor in [
+        "impl-Option%3CT%3E",
+        "impl-Option%3CT%3E-1",
+        "impl-Clone-for-Option%3CT%3E",
+    ] {
+        match document.find_by_anchor(anchor) {
+            Some(AnchorTarget::Impl(imp)) => assert_eq!(imp.anchor.as_deref(), Some(anchor)),
+            other => panic!("{} resolved to {:?}", anchor, other),
+        }
+    }
+}
+
+#[test]
+fn footnote_anchors_resolve_to_footnotes() {
+    let html = Html::parse_document(include_str!(
+        "../examples/std_arch_is_riscv_feature_detected.html"
+    ));
+    let document = parse_document(&html).unwrap();
+
+    for (anchor, label) in [("fn1", "1"), ("#fn4", "4")] {
+        match document.find_by_anchor(anchor) {
+            Some(AnchorTarget::Footnote(footnote)) => assert_eq!(footnote.label, label),
+            other => panic!("{} resolved to {:?}", anchor, other),
+        }
+    }
+}
+
+#[test]
+fn row_anchors_come_from_the_item_link() {
+    let pages = [
+        (
+            include_str!("../examples/tokio_time.html"),
+            ["struct.Sleep", "mod.error", "fn.sleep"],
+        ),
+        (
+            include_str!("../examples/std_time.html"),
+            ["struct.Duration", "struct.Instant", "constant.UNIX_EPOCH"],
+        ),
+        (
+            include_str!("../examples/std_collections.html"),
+            ["mod.binary_heap", "struct.BTreeMap", "enum.Bound"],
+        ),
+    ];
+    for (source, anchors) in pages {
+        let html = Html::parse_document(source);
+        let document = parse_document(&html).unwrap();
+        for anchor in anchors {
+            match document.find_by_anchor(anchor) {
+                Some(AnchorTarget::Row(row)) => assert_eq!(row.anchor.as_deref(), Some(anchor)),
+                other => panic!("{} resolved to {:?}", anchor, other),
+            }
+        }
+    }
+}
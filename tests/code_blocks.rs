@@ -0,0 +1,171 @@
+mod common;
+
+use paradocs::{
+    parse_document, CodeAttribute, CodeBlock, Html, Paragraph, TextPart, TextStyle, TokenKind,
+};
+
+use common::{black_box_with, BLACK_BOX};
+
+fn code_blocks(source: &str, check: impl FnOnce(&[&CodeBlock])) {
+    let html = Html::parse_document(source);
+    let document = parse_document(&html).unwrap();
+    check(&document.code_blocks());
+}
+
+// Puts `snippet` at the start of the black_box description and checks the resulting block.
+fn with_snippet(snippet: &str, check: impl FnOnce(&CodeBlock)) {
+    let source = black_box_with(snippet);
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+    match &document.description[0].contents[0] {
+        Paragraph::Code(block) => check(block),
+        paragraph => panic!("unexpected paragraph {:?}", paragraph),
+    }
+}
+
+fn tokens<'a>(block: &'a CodeBlock) -> impl Iterator<Item = (TokenKind, &'a str)> {
+    block.code.iter().filter_map(|part| match part {
+        TextPart::Token(kind, text) => Some((*kind, &**text)),
+        _ => None,
+    })
+}
+
+#[test]
+fn spans_become_tokens() {
+    code_blocks(include_str!("../examples/slice.html"), |blocks| {
+        assert_eq!(
+            blocks[0].code[..7],
+            [
+                TextPart::BeginStyle(TextStyle::Monospaced),
+                TextPart::Token(TokenKind::Comment, "// slicing a Vec".into()),
+                TextPart::Text("\n".into()),
+                TextPart::Token(TokenKind::Keyword, "let".into()),
+                TextPart::Text(" ".into()),
+                TextPart::Token(TokenKind::Ident, "vec".into()),
+                TextPart::Text(" ".into()),
+            ]
+        );
+
+        let attributes: Vec<_> = blocks
+            .iter()
+            .flat_map(|block| tokens(block))
+            .filter(|(kind, _)| *kind == TokenKind::Attribute)
+            .collect();
+        assert_eq!(attributes.len(), 20);
+        assert!(attributes.iter().all(|(_, text)| text.starts_with('#')));
+    });
+
+    code_blocks(
+        include_str!("../examples/std_option_option.html"),
+        |blocks| {
+            let kinds: Vec<_> = tokens(blocks[1]).map(|(kind, _)| kind).collect();
+            assert!(kinds.contains(&TokenKind::PreludeType));
+            assert!(kinds.contains(&TokenKind::PreludeValue));
+            assert!(tokens(blocks[1]).any(|token| token == (TokenKind::PreludeValue, "Some")));
+        },
+    );
+}
+
+#[test]
+fn both_attribute_classes_are_attributes() {
+    assert_eq!(TokenKind::from_class("attr"), Some(TokenKind::Attribute));
+    assert_eq!(
+        TokenKind::from_class("attribute"),
+        Some(TokenKind::Attribute)
+    );
+    assert_eq!(TokenKind::Attribute.class(), "attr");
+    assert_eq!(TokenKind::from_class("unknown"), None);
+
+    with_snippet(
+        "<div class=\"example-wrap\"><pre class=\"rust rust-example-rendered\"><code>\
+         <span class=\"attr\">#[derive(Debug)]</span>\n<span class=\"kw\">struct</span> Unit;</code></pre></div>",
+        |block| {
+            let found: Vec<_> = tokens(block).collect();
+            assert_eq!(
+                found,
+                [
+                    (TokenKind::Attribute, "#[derive(Debug)]"),
+                    (TokenKind::Keyword, "struct")
+                ]
+            );
+        },
+    );
+}
+
+#[test]
+fn token_classes_round_trip() {
+    let kinds = [
+        TokenKind::Keyword,
+        TokenKind::Keyword2,
+        TokenKind::SelfValue,
+        TokenKind::Ident,
+        TokenKind::Number,
+        TokenKind::String,
+        TokenKind::BoolValue,
+        TokenKind::Op,
+        TokenKind::QuestionMark,
+        TokenKind::Macro,
+        TokenKind::MacroNonterminal,
+        TokenKind::Comment,
+        TokenKind::DocComment,
+        TokenKind::PreludeValue,
+        TokenKind::PreludeType,
+        TokenKind::Lifetime,
+        TokenKind::Attribute,
+    ];
+    for kind in kinds {
+        assert_eq!(TokenKind::from_class(kind.class()), Some(kind));
+    }
+}
+
+#[test]
+fn examples_keep_language_and_playground_url() {
+    code_blocks(BLACK_BOX, |blocks| {
+        assert_eq!(blocks.len(), 6);
+        for block in blocks {
+            assert_eq!(block.language.as_deref(), Some("rust"));
+            assert!(block.attributes.is_empty());
+            assert!(block
+                .playground_url
+                .as_deref()
+                .unwrap()
+                .starts_with("https://play.rust-lang.org/?code="));
+        }
+    });
+
+    code_blocks(include_str!("../examples/tokio_time.html"), |blocks| {
+        assert!(blocks.iter().all(|block| block.playground_url.is_none()));
+    });
+}
+
+#[test]
+fn examples_keep_their_attributes() {
+    code_blocks(include_str!("../examples/slice.html"), |blocks| {
+        let with = |attribute| {
+            blocks
+                .iter()
+                .filter(|block| block.attributes.contains(&attribute))
+                .count()
+        };
+        assert_eq!(with(CodeAttribute::ShouldPanic), 1);
+        assert!(with(CodeAttribute::CompileFail) > 0);
+    });
+
+    code_blocks(include_str!("../examples/std_time.html"), |blocks| {
+        assert!(blocks
+            .iter()
+            .any(|block| block.attributes == [CodeAttribute::Ignore]));
+    });
+
+    with_snippet(
+        "<div class=\"example-wrap edition2021 should_panic\"><pre class=\"language-text\"><code>plain</code></pre></div>",
+        |block| {
+            assert_eq!(block.language.as_deref(), Some("text"));
+            assert_eq!(
+                block.attributes,
+                [CodeAttribute::Edition(2021), CodeAttribute::ShouldPanic]
+            );
+            assert_eq!(block.playground_url, None);
+        },
+    );
+}
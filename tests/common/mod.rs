@@ -0,0 +1,52 @@
+// Not every test crate uses every helper.
+#![allow(dead_code)]
+
+use paradocs::{Document, Impl, ListingType, TextPart};
+
+pub const FIXTURES: &[&str] = &[
+    include_str!("../../examples/lock_api_raw_mutex.html"),
+    include_str!("../../examples/scraper_html_html.html"),
+    include_str!("../../examples/serde_core_de_seq_access.html"),
+    include_str!("../../examples/slice.html"),
+    include_str!("../../examples/std_arch_is_riscv_feature_detected.html"),
+    include_str!("../../examples/std_collections.html"),
+    include_str!("../../examples/std_hint_black_box.html"),
+    include_str!("../../examples/std_io_pipe.html"),
+    include_str!("../../examples/std_io_repeat.html"),
+    include_str!("../../examples/std_option_option.html"),
+    include_str!("../../examples/std_ptr_dyn_metadata.html"),
+    include_str!("../../examples/std_time.html"),
+    include_str!("../../examples/teloxide_types_keyboard.html"),
+    include_str!("../../examples/tokio_time.html"),
+];
+
+pub const BLACK_BOX: &str = include_str!("../../examples/std_hint_black_box.html");
+
+const DOC_BLOCK: &str = "<div class=\"docblock\">";
+
+// The black_box page with `snippet` at the start of its description.
+pub fn black_box_with(snippet: &str) -> String {
+    BLACK_BOX.replacen(DOC_BLOCK, &format!("{}{}", DOC_BLOCK, snippet), 1)
+}
+
+pub fn text(parts: &[TextPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            TextPart::Text(text) | TextPart::Token(_, text) => Some(&**text),
+            _ => None,
+        })
+        .collect()
+}
+
+pub fn impls<'d, 'a>(document: &'d Document<'a>) -> Vec<&'d Impl<'a>> {
+    document
+        .items
+        .iter()
+        .filter_map(|listing| match &listing.kind {
+            ListingType::Impls(impls) => Some(impls),
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
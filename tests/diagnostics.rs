@@ -0,0 +1,95 @@
+mod common;
+
+use paradocs::{parse_document_with_diagnostics, Diagnostics, Html, SkippedNode, Stage};
+
+use common::{black_box_with, BLACK_BOX};
+
+fn skipped(source: &str) -> Vec<SkippedNode> {
+    let html = Html::parse_document(source);
+    let mut diagnostics = Diagnostics::new();
+    parse_document_with_diagnostics(&html, &mut diagnostics).unwrap();
+    diagnostics.skipped
+}
+
+fn summary(skipped: &[SkippedNode]) -> Vec<(Stage, &str, Vec<&str>, usize)> {
+    skipped
+        .iter()
+        .map(|node| {
+            let classes = node.classes.iter().map(String::as_str).collect();
+            (node.stage, &*node.tag, classes, node.position)
+        })
+        .collect()
+}
+
+#[test]
+fn unknown_children_of_main_are_recorded() {
+    let skipped = skipped(include_str!("../examples/lock_api_raw_mutex.html"));
+    assert_eq!(
+        summary(&skipped),
+        [
+            (Stage::Main, "div", vec!["dyn-compatibility-info"], 13),
+            (Stage::Main, "script", vec![], 16),
+        ]
+    );
+    assert_eq!(
+        skipped[0].path.segments().last().map(String::as_str),
+        Some("div.dyn-compatibility-info:nth-child(13)")
+    );
+}
+
+#[test]
+fn positions_match_node_paths() {
+    let fixtures = [
+        include_str!("../examples/lock_api_raw_mutex.html"),
+        include_str!("../examples/scraper_html_html.html"),
+        include_str!("../examples/std_option_option.html"),
+        include_str!("../examples/tokio_time.html"),
+    ];
+    for source in fixtures {
+        for node in skipped(source) {
+            let segment = node.path.segments().last().unwrap();
+            assert!(segment.starts_with(&node.tag), "{}", segment);
+            if !segment.contains('#') {
+                assert!(
+                    segment.ends_with(&format!(":nth-child({})", node.position)),
+                    "{} at {}",
+                    node.position,
+                    segment
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn tooltips_in_doc_blocks_are_recorded() {
+    let source = black_box_with(
+        "<p>first</p>\
+         <div class=\"information\"><div class=\"tooltip compile_fail\">ⓘ</div></div>",
+    );
+    let skipped = skipped(&source);
+    assert_eq!(
+        summary(&skipped),
+        [(Stage::DocBlock, "div", vec!["information"], 2)]
+    );
+    assert_eq!(
+        skipped[0].path.segments().last().map(String::as_str),
+        Some("div.information:nth-child(2)")
+    );
+}
+
+#[test]
+fn clean_pages_skip_nothing() {
+    let skipped = skipped(BLACK_BOX);
+    assert!(skipped.is_empty(), "{:?}", skipped);
+}
+
+#[test]
+fn raw_html_blocks_are_recorded() {
+    let source = black_box_with("<p>first</p><div class=\"custom\">kept</div>");
+    let skipped = skipped(&source);
+    assert_eq!(
+        summary(&skipped),
+        [(Stage::RawHtml, "div", vec!["custom"], 2)]
+    );
+}
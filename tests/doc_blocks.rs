@@ -0,0 +1,221 @@
+mod common;
+
+use paradocs::{parse_document, Alignment, Html, List, Paragraph, TextPart, TextStyle};
+
+use common::{black_box_with, text, BLACK_BOX};
+
+fn paragraph_text(paragraph: &Paragraph) -> String {
+    match paragraph {
+        Paragraph::Text(parts) => text(parts),
+        paragraph => panic!("unexpected paragraph {:?}", paragraph),
+    }
+}
+
+fn description(source: &str, check: impl FnOnce(&[Paragraph])) {
+    let html = Html::parse_document(source);
+    let document = parse_document(&html).unwrap();
+    let contents: Vec<_> = document
+        .description
+        .into_iter()
+        .flat_map(|section| section.contents)
+        .collect();
+    check(&contents);
+}
+
+fn with_snippet(snippet: &str, check: impl FnOnce(&[Paragraph])) {
+    description(&black_box_with(snippet), check);
+}
+
+#[test]
+fn tables_keep_header_rows_and_alignments() {
+    description(
+        include_str!("../examples/std_arch_is_riscv_feature_detected.html"),
+        |contents| {
+            let tables: Vec<_> = contents
+                .iter()
+                .filter_map(|paragraph| match paragraph {
+                    Paragraph::Table(table) => Some(table),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(tables.len(), 2);
+
+            let base = tables[0];
+            let header: Vec<_> = base.header.iter().map(|cell| text(cell)).collect();
+            assert_eq!(header, ["Literal", "Base", "Linux"]);
+            assert_eq!(base.alignments, [Alignment::Left; 3]);
+            let first: Vec<_> = base.rows[0].iter().map(|cell| text(cell)).collect();
+            assert_eq!(first, ["\"rv32e\"", "RV32E", "No"]);
+            assert!(base.rows.iter().all(|row| row.len() == 3));
+        },
+    );
+}
+
+#[test]
+fn nested_tables_stay_in_their_cell() {
+    with_snippet(
+        "<table><thead><tr><th align=\"right\">Outer</th><th>Cell</th></tr></thead><tbody>\
+         <tr><td>a</td><td><table><tbody><tr><td>inner</td></tr><tr><td>rows</td></tr></tbody></table></td></tr>\
+         <tr><td>b</td><td>c</td></tr>\
+         </tbody></table>",
+        |contents| match &contents[0] {
+            Paragraph::Table(table) => {
+                assert_eq!(table.header.len(), 2);
+                assert_eq!(table.alignments, [Alignment::Right, Alignment::None]);
+                let rows: Vec<Vec<_>> = table
+                    .rows
+                    .iter()
+                    .map(|row| row.iter().map(|cell| text(cell)).collect())
+                    .collect();
+                assert_eq!(rows, [["a", ""], ["b", "c"]]);
+            }
+            paragraph => panic!("unexpected paragraph {:?}", paragraph),
+        },
+    );
+}
+
+#[test]
+fn block_quotes_nest_paragraphs() {
+    description(include_str!("../examples/std_io_pipe.html"), |contents| {
+        let quote = contents
+            .iter()
+            .find_map(|paragraph| match paragraph {
+                Paragraph::BlockQuote(quote) => Some(quote),
+                _ => None,
+            })
+            .unwrap();
+        assert!(paragraph_text(&quote[0]).starts_with("Different implementations"));
+    });
+}
+
+#[test]
+fn rules_and_definition_lists() {
+    with_snippet(
+        "<p>before</p><hr><dl><dt>Term</dt><dd><p>First</p><p>Second</p></dd><dt><code>x</code></dt><dd>Inline</dd></dl>",
+        |contents| {
+            assert_eq!(paragraph_text(&contents[0]), "before");
+            assert!(matches!(contents[1], Paragraph::Rule));
+            match &contents[2] {
+                Paragraph::DefinitionList(definitions) => {
+                    assert_eq!(definitions.len(), 2);
+                    assert_eq!(text(&definitions[0].term), "Term");
+                    let details: Vec<_> =
+                        definitions[0].details.iter().map(paragraph_text).collect();
+                    assert_eq!(details, ["First", "Second"]);
+                    assert_eq!(
+                        definitions[1].term[0],
+                        TextPart::BeginStyle(TextStyle::Monospaced)
+                    );
+                    assert_eq!(paragraph_text(&definitions[1].details[0]), "Inline");
+                }
+                paragraph => panic!("unexpected paragraph {:?}", paragraph),
+            }
+        },
+    );
+}
+
+#[test]
+fn ordered_lists_keep_their_start() {
+    description(BLACK_BOX, |contents| {
+        let list = contents
+            .iter()
+            .find_map(|paragraph| match paragraph {
+                Paragraph::List(list) if list.ordered => Some(list),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(list.start, 1);
+        assert_eq!(list.items.len(), 2);
+    });
+
+    with_snippet(
+        "<ol start=\"3\"><li>three</li><li>four</li></ol>",
+        |contents| match &contents[0] {
+            Paragraph::List(List {
+                ordered: true,
+                start: 3,
+                items,
+            }) => assert_eq!(items.len(), 2),
+            paragraph => panic!("unexpected paragraph {:?}", paragraph),
+        },
+    );
+}
+
+#[test]
+fn inline_elements_stay_in_the_paragraph() {
+    with_snippet(
+        "Press <kbd>Ctrl</kbd>+<kbd>C</kbd>, H<sub>2</sub>O, <i>x</i><sup>2</sup>, <b>bold</b>, \
+         <s>old</s> and <abbr title=\"HyperText\">HTML</abbr>.<p>next</p>",
+        |contents| {
+            match &contents[0] {
+                Paragraph::Text(parts) => {
+                    assert_eq!(text(parts), "Press Ctrl+C, H2O, x2, bold, old and HTML.");
+                    assert!(parts.contains(&TextPart::BeginStyle(TextStyle::Monospaced)));
+                    assert!(parts.contains(&TextPart::BeginStyle(TextStyle::Italic)));
+                    assert!(parts.contains(&TextPart::BeginStyle(TextStyle::Bold)));
+                    assert!(parts.contains(&TextPart::BeginStyle(TextStyle::Strikethrough)));
+                }
+                paragraph => panic!("unexpected paragraph {:?}", paragraph),
+            }
+            assert_eq!(paragraph_text(&contents[1]), "next");
+        },
+    );
+}
+
+#[test]
+fn list_items_nest_lists() {
+    let html = Html::parse_document(include_str!(
+        "../examples/std_arch_is_riscv_feature_detected.html"
+    ));
+    let document = parse_document(&html).unwrap();
+    let section = document
+        .description
+        .iter()
+        .find(|section| {
+            section.heading.as_deref().map(text).as_deref() == Some("Performance Hints")
+        })
+        .unwrap();
+
+    let items = match section.contents.last() {
+        Some(Paragraph::List(list)) if !list.ordered => &list.items,
+        paragraph => panic!("unexpected paragraph {:?}", paragraph),
+    };
+    let kernels = ["6.4", "6.13"];
+    assert_eq!(items.len(), kernels.len());
+    for (item, kernel) in items.iter().zip(kernels) {
+        match item.as_slice() {
+            [Paragraph::Text(name), Paragraph::List(nested)] => {
+                assert!(text(name).trim().starts_with("\"unaligned-"));
+                assert_eq!(nested.items.len(), 1);
+                assert_eq!(
+                    paragraph_text(&nested.items[0][0]),
+                    format!(
+                        "Runtime detection requires Linux kernel version {} or later.",
+                        kernel
+                    )
+                );
+            }
+            item => panic!("unexpected item {:?}", item),
+        }
+    }
+}
+
+#[test]
+fn list_items_hold_several_blocks() {
+    with_snippet(
+        "<ul>\n<li>\n<p>First paragraph.</p>\n<p>Second paragraph.</p>\n</li>\n<li>\n<p>Code:</p>\n\
+         <div class=\"example-wrap\"><pre class=\"rust rust-example-rendered\"><code>x</code></pre></div></li>\n</ul>",
+        |contents| match &contents[0] {
+            Paragraph::List(list) => {
+                assert_eq!(list.items.len(), 2);
+                let first: Vec<_> = list.items[0].iter().map(paragraph_text).collect();
+                assert_eq!(first, ["First paragraph.", "Second paragraph."]);
+                assert!(matches!(
+                    list.items[1].as_slice(),
+                    [Paragraph::Text(_), Paragraph::Code(_)]
+                ));
+            }
+            paragraph => panic!("unexpected paragraph {:?}", paragraph),
+        },
+    );
+}
@@ -0,0 +1,46 @@
+use std::fs;
+
+use paradocs::{parse_document, write_doctests, CodeAttribute, Html};
+
+#[test]
+fn skips_examples_that_are_not_meant_to_run() {
+    let html = Html::parse_document(include_str!("../examples/slice.html"));
+    let document = parse_document(&html).unwrap();
+
+    let blocks = document.code_blocks();
+    let compile_fail = blocks
+        .iter()
+        .filter(|block| block.attributes.contains(&CodeAttribute::CompileFail))
+        .count();
+    assert!(compile_fail > 0);
+    assert!(blocks
+        .iter()
+        .filter(|block| block.attributes.contains(&CodeAttribute::CompileFail))
+        .all(|block| !block.is_runnable()));
+
+    let dir = std::env::temp_dir().join("paradocs-tests-slice");
+    let _ = fs::remove_dir_all(&dir);
+    let written = write_doctests(&document, &dir).unwrap();
+
+    let runnable = blocks.iter().filter(|block| block.is_runnable()).count();
+    assert_eq!(written.len(), runnable);
+    assert!(runnable < blocks.iter().filter(|block| block.is_rust()).count());
+}
+
+#[test]
+fn file_names_use_the_full_path() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+
+    let dir = std::env::temp_dir().join("paradocs-tests-option");
+    let _ = fs::remove_dir_all(&dir);
+    let written = write_doctests(&document, &dir).unwrap();
+
+    assert!(!written.is_empty());
+    for (index, file) in written.iter().enumerate() {
+        assert_eq!(
+            file.file_name().unwrap().to_str().unwrap(),
+            format!("std__option__Option_{}.rs", index)
+        );
+    }
+}
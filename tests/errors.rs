@@ -0,0 +1,97 @@
+use paradocs::{parse_document, Expected, Html, ParseError, Stage};
+
+fn error(source: &str, from: &str, to: &str) -> ParseError {
+    let source = source.replacen(from, to, 1);
+    let html = Html::parse_document(&source);
+    parse_document(&html).unwrap_err()
+}
+
+const BLACK_BOX_MAIN: &str =
+    "html:nth-child(1) > body.rustdoc.fn:nth-child(2) > main:nth-child(5) \
+     > div.width-limiter:nth-child(1) > section#main-content.content";
+
+#[test]
+fn missing_main_points_at_the_document() {
+    let error = error(
+        include_str!("../examples/std_hint_black_box.html"),
+        "<section id=\"main-content\"",
+        "<section id=\"content\"",
+    );
+    assert_eq!(error.stage, Stage::Main);
+    assert_eq!(error.expected, Expected::Child("#main-content"));
+    assert!(error.path.segments().is_empty());
+    assert_eq!(
+        error.to_string(),
+        "main: expected child `#main-content` at document"
+    );
+}
+
+#[test]
+fn wrong_heading_class_names_the_heading() {
+    let error = error(
+        include_str!("../examples/std_hint_black_box.html"),
+        "<div class=\"main-heading\">",
+        "<div class=\"heading\">",
+    );
+    assert_eq!(error.stage, Stage::MainHeading);
+    assert_eq!(error.expected, Expected::Class("main-heading"));
+    assert_eq!(
+        error.path.to_string(),
+        format!("{} > div.heading:nth-child(1)", BLACK_BOX_MAIN)
+    );
+}
+
+#[test]
+fn missing_title_points_at_its_parent() {
+    let error = error(
+        include_str!("../examples/std_hint_black_box.html"),
+        "<h1>Function",
+        "<h2>Function",
+    );
+    assert_eq!(error.stage, Stage::MainHeading);
+    assert_eq!(error.expected, Expected::Child("h1"));
+    assert_eq!(
+        error.path.segments().last().map(String::as_str),
+        Some("div.main-heading:nth-child(1)")
+    );
+    assert_eq!(
+        error.to_string(),
+        format!(
+            "main heading: expected child `h1` at {} > div.main-heading:nth-child(1)",
+            BLACK_BOX_MAIN
+        )
+    );
+}
+
+#[test]
+fn older_layouts_report_the_fqn_stage() {
+    let error = error(
+        include_str!("../examples/tokio_time.html"),
+        "<h1 class=\"fqn\">",
+        "<h1 class=\"title\">",
+    );
+    assert_eq!(error.stage, Stage::Fqn);
+    assert_eq!(error.expected, Expected::Class("fqn"));
+    assert_eq!(
+        error.path.to_string(),
+        "html:nth-child(1) > body.rustdoc-page:nth-child(2) \
+         > div#rustdoc_body_wrapper.rustdoc.mod.container-rustdoc > section#main.content \
+         > h1.title:nth-child(1)"
+    );
+}
+
+#[test]
+fn expected_element_sets_list_every_element() {
+    assert_eq!(
+        Expected::AnyElement(&["div", "span"]).to_string(),
+        "expected one of the elements `div`, `span`"
+    );
+}
+
+#[test]
+fn expected_class_sets_list_every_class() {
+    assert_eq!(
+        Expected::AnyClass(&["warning", "note"]).to_string(),
+        "expected one of the classes `warning`, `note`"
+    );
+}
@@ -0,0 +1,174 @@
+mod common;
+
+use paradocs::{parse_document, Document, Footnote, FootnoteReference, Html, Paragraph, TextPart};
+
+use common::black_box_with;
+
+fn collect_references<'d, 'a>(
+    paragraph: &'d Paragraph<'a>,
+    references: &mut Vec<&'d FootnoteReference<'a>>,
+) {
+    let mut from_parts = |parts: &'d [TextPart<'a>]| {
+        references.extend(parts.iter().filter_map(|part| match part {
+            TextPart::FootnoteReference(reference) => Some(reference),
+            _ => None,
+        }))
+    };
+    match paragraph {
+        Paragraph::Text(parts) => from_parts(parts),
+        Paragraph::Table(table) => table
+            .rows
+            .iter()
+            .flatten()
+            .for_each(|cell| from_parts(cell)),
+        Paragraph::List(list) => list
+            .items
+            .iter()
+            .flatten()
+            .for_each(|paragraph| collect_references(paragraph, references)),
+        _ => {}
+    }
+}
+
+fn footnotes<'d, 'a>(
+    document: &'d Document<'a>,
+) -> (Vec<&'d FootnoteReference<'a>>, Vec<&'d Footnote<'a>>) {
+    let mut references = vec![];
+    for paragraph in document
+        .description
+        .iter()
+        .flat_map(|section| &section.contents)
+    {
+        collect_references(paragraph, &mut references);
+    }
+    let footnotes = document
+        .description
+        .iter()
+        .flat_map(|section| &section.footnotes)
+        .collect();
+    (references, footnotes)
+}
+
+#[test]
+fn references_match_definitions() {
+    let html = Html::parse_document(include_str!(
+        "../examples/std_arch_is_riscv_feature_detected.html"
+    ));
+    let document = parse_document(&html).unwrap();
+    let (references, footnotes) = footnotes(&document);
+
+    let labels: Vec<_> = footnotes
+        .iter()
+        .map(|footnote| (&*footnote.id, &*footnote.label, footnote.references.len()))
+        .collect();
+    assert_eq!(
+        labels,
+        [
+            ("fn1", "1", 9),
+            ("fn2", "2", 11),
+            ("fn3", "3", 2),
+            ("fn4", "4", 2)
+        ]
+    );
+
+    assert_eq!(references.len(), 24);
+    for reference in references {
+        let footnote = footnotes
+            .iter()
+            .find(|footnote| footnote.id == reference.target)
+            .unwrap();
+        assert_eq!(reference.label, footnote.label);
+        let id = reference.id.as_deref().unwrap();
+        assert!(footnote.references.iter().any(|back| back == id), "{}", id);
+    }
+}
+
+#[test]
+fn labels_count_from_the_list_start() {
+    let source = black_box_with(
+        "<p>See<sup class=\"footnote-reference\" id=\"fnref7\"><a href=\"#fn7\">7</a></sup>.</p>\
+         <div class=\"footnotes\"><hr><ol start=\"7\">\
+         <li id=\"fn7\"><p>Seven.&nbsp;<a href=\"#fnref7\">↩</a></p></li>\
+         <li id=\"fn8\"><p>Eight.</p></li>\
+         </ol></div>",
+    );
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+    let (references, footnotes) = footnotes(&document);
+
+    let labels: Vec<_> = footnotes
+        .iter()
+        .map(|footnote| (&*footnote.id, &*footnote.label))
+        .collect();
+    assert_eq!(labels, [("fn7", "7"), ("fn8", "8")]);
+    assert_eq!(footnotes[0].references, ["fnref7"]);
+    assert!(footnotes[1].references.is_empty());
+    assert!(matches!(
+        footnotes[0].contents.as_slice(),
+        [Paragraph::Text(parts)] if parts == &[TextPart::Text("Seven.".into())]
+    ));
+
+    assert_eq!(references.len(), 1);
+    assert_eq!(references[0].target, "fn7");
+    assert_eq!(references[0].label, "7");
+    assert_eq!(references[0].id.as_deref(), Some("fnref7"));
+}
+
+#[test]
+fn definitions_sit_with_their_first_reference() {
+    let source = black_box_with(
+        "<p>First<sup class=\"footnote-reference\" id=\"fnref1\"><a href=\"#fn1\">1</a></sup>.</p>\
+         <h2 id=\"second\">Second</h2>\
+         <p>Second<sup class=\"footnote-reference\" id=\"fnref2\"><a href=\"#fn2\">2</a></sup>.</p>\
+         <div class=\"footnotes\"><hr><ol>\
+         <li id=\"fn1\"><p>One.&nbsp;<a href=\"#fnref1\">↩</a></p></li>\
+         <li id=\"fn2\"><p>Two.&nbsp;<a href=\"#fnref2\">↩</a></p></li>\
+         <li id=\"fn3\"><p>Unused.</p></li>\
+         </ol></div>",
+    );
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+
+    let placed: Vec<_> = document
+        .description
+        .iter()
+        .map(|section| {
+            let ids: Vec<_> = section
+                .footnotes
+                .iter()
+                .map(|footnote| &*footnote.id)
+                .collect();
+            (section.anchor.as_deref(), ids)
+        })
+        .filter(|(_, ids)| !ids.is_empty())
+        .collect();
+    // An unreferenced definition stays in the last section, where rustdoc renders the list.
+    assert_eq!(
+        placed,
+        [
+            (None, vec!["fn1"]),
+            (Some("second"), vec!["fn2"]),
+            (Some("how-to-use-this"), vec!["fn3"]),
+        ]
+    );
+
+    let html = Html::parse_document(include_str!(
+        "../examples/std_arch_is_riscv_feature_detected.html"
+    ));
+    let document = parse_document(&html).unwrap();
+    for section in &document.description {
+        for footnote in &section.footnotes {
+            let mut references = vec![];
+            for paragraph in &section.contents {
+                collect_references(paragraph, &mut references);
+            }
+            assert!(
+                references
+                    .iter()
+                    .any(|reference| reference.target == footnote.id),
+                "{}",
+                footnote.id
+            );
+        }
+    }
+}
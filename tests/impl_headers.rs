@@ -0,0 +1,39 @@
+mod common;
+
+use paradocs::{parse_document, Html};
+
+use common::{impls, text};
+
+#[test]
+fn fixture_impl_headers_parse() {
+    for source in [
+        include_str!("../examples/std_option_option.html"),
+        include_str!("../examples/scraper_html_html.html"),
+        include_str!("../examples/serde_core_de_seq_access.html"),
+        include_str!("../examples/std_ptr_dyn_metadata.html"),
+    ] {
+        let html = Html::parse_document(source);
+        let document = parse_document(&html).unwrap();
+        let impls = impls(&document);
+        assert!(!impls.is_empty());
+        for imp in impls {
+            assert!(imp.header.is_some(), "{}", text(&imp.target));
+        }
+    }
+}
+
+#[test]
+fn unparsed_headers_keep_their_target() {
+    let source = include_str!("../examples/std_option_option.html").replacen(
+        "<h3 class=\"code-header\">impl&lt;T&gt; ",
+        "<h3 class=\"code-header\">impl&lt;T&gt; [",
+        1,
+    );
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+    let imp = impls(&document)[0];
+
+    assert_eq!(imp.anchor.as_deref(), Some("impl-Option%3CT%3E"));
+    assert_eq!(imp.header, None);
+    assert_eq!(text(&imp.target), "impl<T> [Option<T>");
+}
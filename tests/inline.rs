@@ -0,0 +1,171 @@
+mod common;
+
+use paradocs::{
+    parse_document, parse_inline, Document, Html, Inline, ListingType, Paragraph, TextPart,
+    TextStyle,
+};
+
+use common::{impls, FIXTURES};
+
+fn paragraph_parts<'d, 'a>(paragraph: &'d Paragraph<'a>, parts: &mut Vec<&'d [TextPart<'a>]>) {
+    match paragraph {
+        Paragraph::Text(text) => parts.push(text),
+        Paragraph::Code(block) => parts.push(&block.code),
+        Paragraph::List(list) => list
+            .items
+            .iter()
+            .flatten()
+            .for_each(|paragraph| paragraph_parts(paragraph, parts)),
+        Paragraph::BlockQuote(contents) | Paragraph::Admonition { contents, .. } => contents
+            .iter()
+            .for_each(|paragraph| paragraph_parts(paragraph, parts)),
+        Paragraph::Table(table) => {
+            parts.extend(table.header.iter().map(Vec::as_slice));
+            parts.extend(table.rows.iter().flatten().map(Vec::as_slice));
+        }
+        Paragraph::DefinitionList(definitions) => {
+            for definition in definitions {
+                parts.push(&definition.term);
+                definition
+                    .details
+                    .iter()
+                    .for_each(|paragraph| paragraph_parts(paragraph, parts));
+            }
+        }
+        Paragraph::Rule | Paragraph::RawHtml(_) => {}
+    }
+}
+
+fn document_parts<'d, 'a>(document: &'d Document<'a>) -> Vec<&'d [TextPart<'a>]> {
+    let mut parts = vec![document.title.as_slice()];
+    parts.extend(document.declaration.as_deref());
+    for section in &document.description {
+        parts.extend(section.heading.as_deref());
+        section
+            .contents
+            .iter()
+            .for_each(|paragraph| paragraph_parts(paragraph, &mut parts));
+    }
+    for listing in &document.items {
+        parts.push(&listing.heading);
+        match &listing.kind {
+            ListingType::Table(rows) => {
+                for row in rows {
+                    parts.push(&row.name);
+                    parts.push(&row.summary);
+                }
+            }
+            ListingType::Fields(items) => parts.extend(items.iter().map(|item| &*item.name)),
+            ListingType::Impls(impls) => {
+                for imp in impls {
+                    parts.push(&imp.target);
+                    parts.extend(imp.items.iter().map(|item| &*item.name));
+                }
+            }
+        }
+    }
+    parts
+}
+
+#[test]
+fn parts_round_trip_through_the_tree() {
+    for source in FIXTURES {
+        let html = Html::parse_document(source);
+        let document = parse_document(&html).unwrap();
+
+        for parts in document_parts(&document) {
+            let tree = Inline::from_parts(parts).unwrap();
+            assert_eq!(Inline::to_parts(&tree), parts);
+        }
+    }
+}
+
+#[test]
+fn unbalanced_styles_have_no_tree() {
+    let unclosed = [
+        TextPart::BeginStyle(TextStyle::Bold),
+        TextPart::Text("bold".into()),
+    ];
+    assert_eq!(Inline::from_parts(&unclosed), None);
+
+    let stray = [TextPart::Text("text".into()), TextPart::EndStyle];
+    assert_eq!(Inline::from_parts(&stray), None);
+}
+
+#[test]
+fn parse_inline_builds_the_tree_from_html() {
+    let html = Html::parse_fragment(
+        "<p>Returns <code>None</code> if <a href=\"#x\"><em>empty</em></a>.<br>\n</p>",
+    );
+    let p = html
+        .root_element()
+        .children()
+        .find_map(scraper::ElementRef::wrap)
+        .unwrap();
+
+    assert_eq!(
+        parse_inline(p),
+        [
+            Inline::Text("Returns ".into()),
+            Inline::Code(vec![Inline::Text("None".into())]),
+            Inline::Text(" if ".into()),
+            Inline::Link {
+                href: "#x".into(),
+                children: vec![Inline::Emphasis(vec![Inline::Text("empty".into())])],
+            },
+            Inline::Text(".".into()),
+            Inline::LineBreak,
+            Inline::Text("\n".into()),
+        ]
+    );
+}
+
+#[test]
+fn links_without_href_keep_their_text() {
+    let html = Html::parse_document(include_str!("../examples/teloxide_types_keyboard.html"));
+    let document = parse_document(&html).unwrap();
+
+    let names: Vec<_> = impls(&document)
+        .into_iter()
+        .flat_map(|imp| &imp.items)
+        .filter_map(|item| item.signature())
+        .map(|signature| signature.name.into_owned())
+        .collect();
+    for name in ["subset", "sculpt", "lift_into", "get_hash"] {
+        assert!(names.iter().any(|found| found == name), "{}", name);
+    }
+}
+
+#[test]
+fn line_breaks_flatten_to_newlines() {
+    let html = Html::parse_fragment("<p>one<br>two</p>");
+    let p = html
+        .root_element()
+        .children()
+        .find_map(scraper::ElementRef::wrap)
+        .unwrap();
+
+    let tree = parse_inline(p);
+    assert_eq!(
+        tree,
+        [
+            Inline::Text("one".into()),
+            Inline::LineBreak,
+            Inline::Text("two".into()),
+        ]
+    );
+
+    let parts = Inline::to_parts(&tree);
+    assert_eq!(
+        parts,
+        [
+            TextPart::Text("one".into()),
+            TextPart::Text("\n".into()),
+            TextPart::Text("two".into()),
+        ]
+    );
+
+    let rebuilt = Inline::from_parts(&parts).unwrap();
+    assert_eq!(rebuilt[1], Inline::Text("\n".into()));
+    assert_eq!(Inline::to_parts(&rebuilt), parts);
+}
@@ -0,0 +1,357 @@
+mod common;
+
+use paradocs::{
+    detect_version, parse_document, Document, Expected, Html, ImplCategory, ItemKind, ListingType,
+};
+
+use common::text;
+
+use ImplCategory::{Auto, Blanket, Foreign, Implementors, Inherent, Trait};
+
+type Listing = (&'static str, &'static str, usize, Option<ImplCategory>);
+
+fn listings(document: &Document) -> Vec<(String, &'static str, usize, Option<ImplCategory>)> {
+    document
+        .items
+        .iter()
+        .map(|listing| {
+            let (kind, count) = match &listing.kind {
+                ListingType::Table(rows) => ("table", rows.len()),
+                ListingType::Fields(items) => ("fields", items.len()),
+                ListingType::Impls(impls) => ("impls", impls.len()),
+            };
+            (
+                text(&listing.heading).trim().to_string(),
+                kind,
+                count,
+                listing.category,
+            )
+        })
+        .collect()
+}
+
+fn check(
+    source: &str,
+    minor: Option<u32>,
+    kind: ItemKind,
+    path: &str,
+    title: &str,
+    expected: &[Listing],
+) {
+    let html = Html::parse_document(source);
+    let document = parse_document(&html).unwrap();
+
+    assert_eq!(
+        document.version.as_ref().map(|version| version.minor),
+        minor
+    );
+    assert_eq!(document.kind(), Some(kind));
+    assert_eq!(document.path().unwrap().to_string(), path);
+    assert_eq!(text(&document.title), title);
+
+    let expected: Vec<_> = expected
+        .iter()
+        .map(|&(heading, kind, count, category)| (heading.to_string(), kind, count, category))
+        .collect();
+    assert_eq!(listings(&document), expected);
+}
+
+const TOKIO_TIME: &[Listing] = &[
+    ("Re-exports", "table", 1, None),
+    ("Modules", "table", 1, None),
+    ("Structs", "table", 4, None),
+    ("Enums", "table", 1, None),
+    ("Functions", "table", 9, None),
+];
+
+const SCRAPER_HTML: &[Listing] = &[
+    ("Fields", "fields", 3, None),
+    ("Methods", "impls", 1, Some(Inherent)),
+    ("Trait Implementations", "impls", 7, Some(Trait)),
+    ("Auto Trait Implementations", "impls", 5, Some(Auto)),
+    ("Blanket Implementations", "impls", 8, Some(Blanket)),
+];
+
+const SLICE: &[Listing] = &[
+    ("Implementations", "impls", 4, Some(Inherent)),
+    ("Trait Implementations", "impls", 33, Some(Trait)),
+    ("Auto Trait Implementations", "impls", 12, Some(Auto)),
+    ("Blanket Implementations", "impls", 6, Some(Blanket)),
+];
+
+#[test]
+fn scraper_html_html() {
+    check(
+        include_str!("../examples/scraper_html_html.html"),
+        Some(45),
+        ItemKind::Struct,
+        "scraper::html::Html",
+        "Struct scraper::html::Html",
+        SCRAPER_HTML,
+    );
+}
+
+#[test]
+fn slice() {
+    check(
+        include_str!("../examples/slice.html"),
+        Some(56),
+        ItemKind::Primitive,
+        "std::slice",
+        "Primitive Type slice",
+        SLICE,
+    );
+}
+
+#[test]
+fn std_ptr_dyn_metadata() {
+    check(
+        include_str!("../examples/std_ptr_dyn_metadata.html"),
+        Some(56),
+        ItemKind::Struct,
+        "std::ptr::DynMetadata",
+        "Struct std::ptr::DynMetadata",
+        &[
+            ("Implementations", "impls", 1, Some(Inherent)),
+            ("Trait Implementations", "impls", 11, Some(Trait)),
+            ("Auto Trait Implementations", "impls", 2, Some(Auto)),
+            ("Blanket Implementations", "impls", 8, Some(Blanket)),
+        ],
+    );
+}
+
+#[test]
+fn tokio_time() {
+    check(
+        include_str!("../examples/tokio_time.html"),
+        Some(58),
+        ItemKind::Module,
+        "tokio::time",
+        "Module tokio::time",
+        TOKIO_TIME,
+    );
+}
+
+#[test]
+fn teloxide_types_keyboard() {
+    check(
+        include_str!("../examples/teloxide_types_keyboard.html"),
+        Some(58),
+        ItemKind::Struct,
+        "teloxide::types::KeyboardButton",
+        "Struct teloxide::types::KeyboardButton",
+        &[
+            ("Fields", "fields", 2, None),
+            ("Implementations", "impls", 1, Some(Inherent)),
+            ("Trait Implementations", "impls", 9, Some(Trait)),
+            ("Auto Trait Implementations", "impls", 5, Some(Auto)),
+            ("Blanket Implementations", "impls", 16, Some(Blanket)),
+        ],
+    );
+}
+
+#[test]
+fn std_collections() {
+    check(
+        include_str!("../examples/std_collections.html"),
+        Some(70),
+        ItemKind::Module,
+        "std::collections",
+        "Module std::collections",
+        &[
+            ("Modules", "table", 5, None),
+            ("Structs", "table", 4, None),
+            ("Enums", "table", 2, None),
+        ],
+    );
+}
+
+#[test]
+fn std_option_option() {
+    check(
+        include_str!("../examples/std_option_option.html"),
+        Some(75),
+        ItemKind::Enum,
+        "std::option::Option",
+        "Enum std::option::Option",
+        &[
+            ("Variants", "fields", 2, None),
+            ("Implementations", "impls", 2, Some(Inherent)),
+            ("Trait Implementations", "impls", 3, Some(Trait)),
+            ("Auto Trait Implementations", "impls", 2, Some(Auto)),
+            ("Blanket Implementations", "impls", 2, Some(Blanket)),
+        ],
+    );
+}
+
+#[test]
+fn std_time() {
+    check(
+        include_str!("../examples/std_time.html"),
+        Some(90),
+        ItemKind::Module,
+        "std::time",
+        "Module std::time",
+        &[
+            ("Structs", "table", 5, None),
+            ("Constants", "table", 1, None),
+        ],
+    );
+}
+
+#[test]
+fn serde_core_de_seq_access() {
+    check(
+        include_str!("../examples/serde_core_de_seq_access.html"),
+        Some(95),
+        ItemKind::Trait,
+        "serde_core::de::SeqAccess",
+        "Trait serde_core::de::SeqAccess",
+        &[
+            ("Required Associated Types", "fields", 1, None),
+            ("Required Methods", "fields", 1, None),
+            ("Provided Methods", "fields", 2, None),
+            (
+                "Implementations on Foreign Types",
+                "impls",
+                1,
+                Some(Foreign),
+            ),
+            ("Implementors", "impls", 2, Some(Implementors)),
+        ],
+    );
+}
+
+#[test]
+fn lock_api_raw_mutex() {
+    check(
+        include_str!("../examples/lock_api_raw_mutex.html"),
+        Some(95),
+        ItemKind::Trait,
+        "lock_api::RawMutex",
+        "Trait lock_api::RawMutex",
+        &[
+            ("Required Associated Constants", "fields", 1, None),
+            ("Required Associated Types", "fields", 1, None),
+            ("Required Methods", "fields", 3, None),
+            ("Provided Methods", "fields", 1, None),
+            ("Implementors", "impls", 0, Some(Implementors)),
+        ],
+    );
+}
+
+#[test]
+fn std_io_pipe() {
+    check(
+        include_str!("../examples/std_io_pipe.html"),
+        Some(97),
+        ItemKind::Function,
+        "std::io::pipe",
+        "Function std::io::pipe",
+        &[],
+    );
+}
+
+#[test]
+fn std_arch_is_riscv_feature_detected() {
+    check(
+        include_str!("../examples/std_arch_is_riscv_feature_detected.html"),
+        Some(97),
+        ItemKind::Macro,
+        "std::arch::is_riscv_feature_detected",
+        "Macro std::arch::is_riscv_feature_detected",
+        &[],
+    );
+}
+
+#[test]
+fn std_hint_black_box() {
+    check(
+        include_str!("../examples/std_hint_black_box.html"),
+        Some(97),
+        ItemKind::Function,
+        "std::hint::black_box",
+        "Function std::hint::black_box",
+        &[],
+    );
+}
+
+#[test]
+fn std_io_repeat() {
+    check(
+        include_str!("../examples/std_io_repeat.html"),
+        Some(97),
+        ItemKind::Struct,
+        "std::io::Repeat",
+        "Struct std::io::Repeat",
+        &[
+            ("Trait Implementations", "impls", 2, Some(Trait)),
+            ("Auto Trait Implementations", "impls", 7, Some(Auto)),
+            ("Blanket Implementations", "impls", 7, Some(Blanket)),
+        ],
+    );
+}
+
+#[test]
+fn unversioned_pages_fall_back_to_matching_layout() {
+    let pages = [
+        (
+            include_str!("../examples/tokio_time.html")
+                .replace("-1.58.0-nightly-c390d69a6", "")
+                .replace("-20211028", ""),
+            ItemKind::Module,
+            "tokio::time",
+            "Module tokio::time",
+            TOKIO_TIME,
+        ),
+        (
+            include_str!("../examples/scraper_html_html.html")
+                .replace("-1.45.0-nightly-65b448273", ""),
+            ItemKind::Struct,
+            "scraper::html::Html",
+            "Struct scraper::html::Html",
+            SCRAPER_HTML,
+        ),
+        (
+            include_str!("../examples/slice.html").replace("1.56.1", ""),
+            ItemKind::Primitive,
+            "std::slice",
+            "Primitive Type slice",
+            SLICE,
+        ),
+    ];
+
+    for (source, kind, path, title, expected) in pages {
+        assert_eq!(detect_version(&Html::parse_document(&source)), None);
+        check(&source, None, kind, path, title, expected);
+    }
+}
+
+#[test]
+fn errors_name_the_selector_of_the_detected_layout() {
+    let expected = |source: &str| {
+        let html = Html::parse_document(source);
+        parse_document(&html).unwrap_err().expected
+    };
+
+    assert_eq!(
+        expected(r#"<div id="rustdoc-vars" data-rustdoc-version="1.90.0"></div>"#),
+        Expected::Child("#main-content")
+    );
+    assert_eq!(
+        expected(r#"<meta name="generator" content="rustdoc 1.45.0"><div></div>"#),
+        Expected::Child("#main")
+    );
+    assert_eq!(
+        expected(
+            r#"<div id="rustdoc-vars" data-rustdoc-version="1.90.0"></div><section id="main-content"></section>"#
+        ),
+        Expected::Child("div.main-heading")
+    );
+    assert_eq!(
+        expected(
+            r#"<meta name="generator" content="rustdoc 1.58.0"><section id="main"></section>"#
+        ),
+        Expected::Child("h1.fqn")
+    );
+}
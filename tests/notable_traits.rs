@@ -0,0 +1,65 @@
+mod common;
+
+use paradocs::{parse_document, Document, Html, Item};
+
+use common::{impls, text};
+
+fn items_with_notable_traits<'d, 'a>(document: &'d Document<'a>) -> Vec<&'d Item<'a>> {
+    impls(document)
+        .into_iter()
+        .flat_map(|imp| &imp.items)
+        .filter(|item| !item.notable_traits.is_empty())
+        .collect()
+}
+
+#[test]
+fn json_tooltips() {
+    let html = Html::parse_document(include_str!("../examples/std_io_repeat.html"));
+    let document = parse_document(&html).unwrap();
+    let items = items_with_notable_traits(&document);
+
+    let anchors: Vec<_> = items
+        .iter()
+        .map(|item| item.anchor.as_deref().unwrap())
+        .collect();
+    assert_eq!(anchors, ["method.bytes", "method.chain", "method.take"]);
+
+    let bytes = &items[0].notable_traits;
+    assert_eq!(bytes.len(), 1);
+    assert_eq!(
+        text(&bytes[0].target),
+        "impl<R: Read> Iterator for Bytes<R>"
+    );
+    let header = bytes[0].header.as_ref().unwrap();
+    assert_eq!(header.trait_path.as_ref().unwrap().to_string(), "Iterator");
+    assert_eq!(header.self_ty.to_string(), "Bytes<R>");
+    let associated: Vec<_> = bytes[0]
+        .items
+        .iter()
+        .map(|item| text(&item.name).trim().to_string())
+        .collect();
+    assert_eq!(associated, ["type Item = Result<u8>;"]);
+
+    let chain = &items[1].notable_traits;
+    assert_eq!(chain.len(), 1);
+    let header = chain[0].header.as_ref().unwrap();
+    assert_eq!(header.trait_path.as_ref().unwrap().to_string(), "Read");
+    assert!(chain[0].items.is_empty());
+}
+
+#[test]
+fn inline_tooltips() {
+    let html = Html::parse_document(include_str!("../examples/slice.html"));
+    let document = parse_document(&html).unwrap();
+    let items = items_with_notable_traits(&document);
+
+    assert_eq!(items.len(), 53);
+    let range = items
+        .iter()
+        .find(|item| item.anchor.as_deref() == Some("method.as_ptr_range"))
+        .unwrap();
+    assert_eq!(range.notable_traits.len(), 1);
+    let header = range.notable_traits[0].header.as_ref().unwrap();
+    assert_eq!(header.self_ty.to_string(), "Range<A>");
+    assert_eq!(header.where_clause.len(), 1);
+}
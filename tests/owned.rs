@@ -0,0 +1,36 @@
+mod common;
+
+use std::thread;
+
+use paradocs::{parse_document, Document, Html};
+
+use common::FIXTURES;
+
+// Parses a copy of `source` and drops both the copy and the `Html` before returning.
+fn parse_owned(source: &str) -> (Document<'static>, String) {
+    let source = source.to_string();
+    let html = Html::parse_document(&source);
+    let document = parse_document(&html).unwrap();
+    let debug = format!("{:?}", document);
+    let owned = document.into_owned();
+    drop(html);
+    drop(source);
+    (owned, debug)
+}
+
+#[test]
+fn owned_documents_outlive_the_html() {
+    for source in FIXTURES {
+        let (owned, borrowed) = parse_owned(source);
+        assert_eq!(format!("{:?}", owned), borrowed);
+    }
+}
+
+#[test]
+fn owned_documents_move_across_threads() {
+    let (owned, borrowed) = parse_owned(FIXTURES[0]);
+    let debug = thread::spawn(move || format!("{:?}", owned))
+        .join()
+        .unwrap();
+    assert_eq!(debug, borrowed);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "serde")]
+
+mod common;
+
+use paradocs::{parse_document, Document, Html, List, Paragraph, TextPart, TextStyle, TokenKind};
+
+use common::FIXTURES;
+use serde_json::{json, Value};
+
+fn to_value(value: impl serde::Serialize) -> Value {
+    serde_json::to_value(value).unwrap()
+}
+
+#[test]
+fn text_parts_are_adjacently_tagged() {
+    assert_eq!(
+        to_value(TextPart::Text("std".into())),
+        json!({"type": "text", "value": "std"})
+    );
+    assert_eq!(
+        to_value(TextPart::Token(TokenKind::Keyword, "fn".into())),
+        json!({"type": "token", "value": ["keyword", "fn"]})
+    );
+    assert_eq!(
+        to_value(TextPart::BeginStyle(TextStyle::Link("#x".into()))),
+        json!({"type": "begin_style", "value": {"type": "link", "value": "#x"}})
+    );
+    assert_eq!(to_value(TextPart::EndStyle), json!({"type": "end_style"}));
+}
+
+#[test]
+fn paragraphs_match_the_documented_shape() {
+    assert_eq!(to_value(Paragraph::Rule), json!({"type": "rule"}));
+
+    let list = Paragraph::List(List {
+        ordered: true,
+        start: 1,
+        items: vec![vec![Paragraph::Text(vec![TextPart::Text("one".into())])]],
+    });
+    assert_eq!(
+        to_value(list),
+        json!({
+            "type": "list",
+            "value": {
+                "ordered": true,
+                "start": 1,
+                "items": [[{"type": "text", "value": [{"type": "text", "value": "one"}]}]],
+            },
+        })
+    );
+
+    let html = Html::parse_document(include_str!("../examples/std_hint_black_box.html"));
+    let document = parse_document(&html).unwrap();
+    let paragraph = to_value(&document.description[0].contents[0]);
+    assert_eq!(paragraph["type"], "text");
+    assert_eq!(
+        paragraph["value"][0],
+        json!({"type": "text", "value": "An identity function that "})
+    );
+}
+
+#[test]
+fn listings_match_the_documented_shape() {
+    let html = Html::parse_document(include_str!("../examples/std_collections.html"));
+    let document = parse_document(&html).unwrap();
+    let listing = to_value(&document.items[0]);
+
+    assert_eq!(listing["anchor"], "modules");
+    assert_eq!(listing["category"], Value::Null);
+    assert_eq!(listing["kind"]["type"], "table");
+    assert_eq!(
+        listing["kind"]["value"][0],
+        json!({
+            "name": [
+                {"type": "begin_style", "value": {"type": "link", "value": "binary_heap/index.html"}},
+                {"type": "text", "value": "binary_heap"},
+                {"type": "end_style"},
+            ],
+            "info": {"stability": null, "portability": null, "deprecation": null},
+            "summary": [
+                {"type": "text", "value": "A priority queue implemented with a binary heap."},
+            ],
+            "anchor": "mod.binary_heap",
+        })
+    );
+}
+
+#[test]
+fn documents_round_trip_through_json() {
+    for source in FIXTURES {
+        let html = Html::parse_document(source);
+        let document = parse_document(&html).unwrap();
+        let json = serde_json::to_string(&document).unwrap();
+
+        let owned: Document<'static> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&owned).unwrap(), json);
+    }
+}
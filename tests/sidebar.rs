@@ -0,0 +1,200 @@
+mod common;
+
+use paradocs::{parse_document, parse_sidebar_items, Html, ItemKind, SiblingKind};
+
+use common::text;
+
+fn crate_and_version(source: &str) -> (Option<String>, Option<String>) {
+    let html = Html::parse_document(source);
+    let sidebar = parse_document(&html).unwrap().sidebar;
+    (
+        sidebar.crate_name.map(|name| name.into_owned()),
+        sidebar.version.map(|version| version.into_owned()),
+    )
+}
+
+fn items_src(source: &str) -> Option<String> {
+    let html = Html::parse_document(source);
+    let document = parse_document(&html).unwrap();
+    document.sidebar.items_src.map(|src| src.into_owned())
+}
+
+#[test]
+fn items_src_matches_plain_and_versioned_scripts() {
+    assert_eq!(
+        items_src(include_str!("../examples/std_option_option.html")).as_deref(),
+        Some("sidebar-items.js")
+    );
+    assert_eq!(
+        items_src(include_str!("../examples/std_time.html")).as_deref(),
+        Some("../sidebar-items.js")
+    );
+    for source in [
+        include_str!("../examples/std_hint_black_box.html"),
+        include_str!("../examples/std_io_pipe.html"),
+        include_str!("../examples/std_arch_is_riscv_feature_detected.html"),
+    ] {
+        assert_eq!(items_src(source).as_deref(), Some("sidebar-items1.97.0.js"));
+    }
+}
+
+#[test]
+fn siblings_come_from_the_items_script() {
+    let groups =
+        parse_sidebar_items(include_str!("../examples/std_option_sidebar_items.js")).unwrap();
+    let groups: Vec<_> = groups
+        .iter()
+        .map(|group| {
+            let names: Vec<_> = group.items.iter().map(|item| &*item.name).collect();
+            (&group.kind, names)
+        })
+        .collect();
+
+    assert_eq!(
+        groups,
+        [
+            (&SiblingKind::Known(ItemKind::Enum), vec!["Option"]),
+            (
+                &SiblingKind::Known(ItemKind::Struct),
+                vec!["IntoIter", "Iter", "IterMut"]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn unknown_sibling_kinds_keep_their_key() {
+    let groups =
+        parse_sidebar_items(r#"window.SIDEBAR_ITEMS = {"fn":["sleep"],"opaque":["Foo"]};"#)
+            .unwrap();
+    let kinds: Vec<_> = groups.into_iter().map(|group| group.kind).collect();
+
+    assert_eq!(
+        kinds,
+        [
+            SiblingKind::Known(ItemKind::Function),
+            SiblingKind::Unknown("opaque".into()),
+        ]
+    );
+}
+
+#[test]
+fn crate_name_and_version() {
+    let pages = [
+        (
+            include_str!("../examples/std_option_option.html"),
+            "std",
+            Some("1.75.0"),
+        ),
+        (
+            include_str!("../examples/std_hint_black_box.html"),
+            "std",
+            Some("1.97.0-nightly"),
+        ),
+        (
+            include_str!("../examples/lock_api_raw_mutex.html"),
+            "lock_api",
+            Some("0.4.14"),
+        ),
+        (
+            include_str!("../examples/std_collections.html"),
+            "std",
+            None,
+        ),
+        (include_str!("../examples/tokio_time.html"), "tokio", None),
+        (
+            include_str!("../examples/scraper_html_html.html"),
+            "scraper",
+            None,
+        ),
+    ];
+    for (source, crate_name, version) in pages {
+        let (found_name, found_version) = crate_and_version(source);
+        assert_eq!(found_name.as_deref(), Some(crate_name));
+        assert_eq!(found_version.as_deref(), version);
+    }
+}
+
+#[test]
+fn sections_keep_titles_anchors_and_links() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+    let sections: Vec<_> = document
+        .sidebar
+        .sections
+        .iter()
+        .map(|section| {
+            (
+                section.title.as_deref().map(text),
+                section.anchor.as_deref(),
+                section.links.len(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        sections,
+        [
+            (Some("Variants".to_string()), Some("variants"), 2),
+            (Some("Methods".to_string()), Some("implementations"), 4),
+            (
+                Some("Trait Implementations".to_string()),
+                Some("trait-implementations"),
+                3
+            ),
+            (
+                Some("Auto Trait Implementations".to_string()),
+                Some("synthetic-implementations"),
+                2
+            ),
+            (
+                Some("Blanket Implementations".to_string()),
+                Some("blanket-implementations"),
+                2
+            ),
+        ]
+    );
+
+    let first = &document.sidebar.sections[0].links[0];
+    assert_eq!(text(&first.name), "None");
+    assert_eq!(first.href, "#variant.None");
+    for section in &document.sidebar.sections {
+        let anchor = section.anchor.as_deref().unwrap();
+        assert!(document.find_by_anchor(anchor).is_some(), "{}", anchor);
+    }
+}
+
+#[test]
+fn page_table_of_contents_is_not_a_section() {
+    for source in [
+        include_str!("../examples/std_hint_black_box.html"),
+        include_str!("../examples/lock_api_raw_mutex.html"),
+        include_str!("../examples/std_time.html"),
+    ] {
+        let html = Html::parse_document(source);
+        let document = parse_document(&html).unwrap();
+        for section in &document.sidebar.sections {
+            assert_ne!(
+                section.title.as_deref().map(text).as_deref(),
+                Some("Sections")
+            );
+            assert!(section
+                .links
+                .iter()
+                .all(|link| link.href != "#when-is-this-useful"));
+        }
+    }
+}
+
+#[test]
+fn untitled_sections_hold_the_links() {
+    let html = Html::parse_document(include_str!("../examples/tokio_time.html"));
+    let document = parse_document(&html).unwrap();
+    match document.sidebar.sections.as_slice() {
+        [section] => {
+            assert!(section.title.is_none() && section.anchor.is_none());
+            let names: Vec<_> = section.links.iter().map(|link| text(&link.name)).collect();
+            assert_eq!(names, ["Modules", "Structs", "Enums", "Functions"]);
+        }
+        sections => panic!("unexpected sections {:?}", sections),
+    }
+}
@@ -0,0 +1,106 @@
+use paradocs::{parse_document, Bound, Html, Impl, Item, TraitInfo, TraitItem};
+
+fn anchors(items: &[&Item]) -> Vec<String> {
+    items
+        .iter()
+        .map(|item| item.anchor.as_deref().unwrap_or_default().to_string())
+        .collect()
+}
+
+fn self_types(impls: &[&Impl]) -> Vec<String> {
+    impls
+        .iter()
+        .map(|imp| imp.header.as_ref().unwrap().self_ty.to_string())
+        .collect()
+}
+
+fn with_trait_info(source: &str, check: impl FnOnce(TraitInfo)) {
+    let html = Html::parse_document(source);
+    let document = parse_document(&html).unwrap();
+    check(document.trait_info().unwrap());
+}
+
+#[test]
+fn lock_api_raw_mutex() {
+    with_trait_info(
+        include_str!("../examples/lock_api_raw_mutex.html"),
+        |info| {
+            assert_eq!(
+                anchors(&info.required_methods),
+                ["tymethod.lock", "tymethod.try_lock", "tymethod.unlock"]
+            );
+            assert_eq!(anchors(&info.provided_methods), ["method.is_locked"]);
+
+            assert_eq!(info.associated_consts.len(), 1);
+            let init = &info.associated_consts[0];
+            assert!(init.required);
+            assert!(matches!(
+                &init.declaration,
+                Some(TraitItem::Const { name, ty, default: None }) if name == "INIT" && ty.to_string() == "Self"
+            ));
+
+            assert_eq!(info.associated_types.len(), 1);
+            let marker = &info.associated_types[0];
+            assert!(marker.required);
+            assert!(matches!(
+                &marker.declaration,
+                Some(TraitItem::Type { name, bounds, default: None, .. }) if name == "GuardMarker" && bounds.is_empty()
+            ));
+
+            assert!(info.implementors.is_empty());
+            assert!(info.auto_implementors.is_empty());
+            assert!(info.foreign_impls.is_empty());
+        },
+    );
+}
+
+#[test]
+fn serde_core_de_seq_access() {
+    with_trait_info(
+        include_str!("../examples/serde_core_de_seq_access.html"),
+        |info| {
+            assert_eq!(
+                anchors(&info.required_methods),
+                ["tymethod.next_element_seed"]
+            );
+            assert_eq!(
+                anchors(&info.provided_methods),
+                ["method.next_element", "method.size_hint"]
+            );
+
+            assert!(info.associated_consts.is_empty());
+            assert_eq!(info.associated_types.len(), 1);
+            let error = &info.associated_types[0];
+            assert!(error.required);
+            match &error.declaration {
+                Some(TraitItem::Type {
+                    name,
+                    bounds,
+                    default: None,
+                    ..
+                }) => {
+                    assert_eq!(name, "Error");
+                    assert!(matches!(
+                        bounds.as_slice(),
+                        [Bound::Trait { path, .. }] if path.to_string() == "Error"
+                    ));
+                }
+                declaration => panic!("unexpected declaration {:?}", declaration),
+            }
+
+            assert_eq!(
+                self_types(&info.implementors),
+                ["MapDeserializer<'de, I, E>", "SeqDeserializer<I, E>"]
+            );
+            assert!(info.auto_implementors.is_empty());
+            assert_eq!(self_types(&info.foreign_impls), ["&mut A"]);
+        },
+    );
+}
+
+#[test]
+fn non_trait_pages_have_no_trait_info() {
+    let html = Html::parse_document(include_str!("../examples/std_option_option.html"));
+    let document = parse_document(&html).unwrap();
+    assert!(document.trait_info().is_none());
+}